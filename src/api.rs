@@ -1,78 +1,474 @@
 use graphql_client::{GraphQLQuery, Response};
-use reqwest::Client;
-use std::error::Error;
+use reqwest::{Client, StatusCode, header::HeaderMap};
+use std::{
+    fmt,
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub const DEFAULT_ENDPOINT: &str = "https://api.linear.app/graphql";
+
+// transient failures are retried this many times before giving up
+const MAX_RETRIES: u32 = 3;
+const BASE_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+// start spacing requests out once less than this share of the budget is left
+const SLOWDOWN_THRESHOLD: f64 = 0.1;
+
+#[derive(Debug)]
+pub enum ApiError {
+    InvalidToken,
+    Http(reqwest::Error),
+    Status { status: StatusCode, body: String },
+    Decode(serde_json::Error),
+    GraphQL(Vec<graphql_client::Error>),
+    RateLimited,
+    MissingData,
+}
+
+impl ApiError {
+    // Linear reports an unknown id as an input error ("Entity not found: Issue"), which for a
+    // lookup by id means there is no such issue
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            ApiError::GraphQL(errors) => errors
+                .iter()
+                .any(|error| error_code(error) == Some("INPUT_ERROR")),
+            _ => false,
+        }
+    }
+
+    fn is_rate_limited(errors: &[graphql_client::Error]) -> bool {
+        errors
+            .iter()
+            .any(|error| error_code(error) == Some("RATELIMITED"))
+    }
+}
+
+// the kind of error, in the `extensions` Linear adds to every GraphQL error
+fn error_code(error: &graphql_client::Error) -> Option<&str> {
+    error.extensions.as_ref()?.get("code")?.as_str()
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::InvalidToken => write!(f, "LINEAR_API_TOKEN is not a valid header value"),
+            ApiError::Http(e) => write!(f, "Request failed: {}", e),
+            ApiError::Status { status, body } => write!(f, "Linear returned {}: {}", status, body),
+            ApiError::Decode(e) => write!(f, "Unexpected response: {}", e),
+            ApiError::GraphQL(errors) => {
+                let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+                write!(f, "{}", messages.join("; "))
+            }
+            ApiError::RateLimited => write!(f, "Rate limited by Linear, try again shortly"),
+            ApiError::MissingData => write!(f, "Linear returned no data"),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ApiError::Http(e) => Some(e),
+            ApiError::Decode(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ApiError {
+    fn from(e: reqwest::Error) -> Self {
+        ApiError::Http(e)
+    }
+}
+
+// one of Linear's rate limit buckets, as reported by the X-RateLimit-* headers
+#[derive(Debug, Default, Clone, Copy)]
+struct Budget {
+    limit: u64,
+    remaining: u64,
+    reset_at: Option<SystemTime>,
+}
+
+impl Budget {
+    fn from_headers(headers: &HeaderMap, kind: &str) -> Option<Self> {
+        let header = |name: &str| -> Option<u64> {
+            headers
+                .get(format!("x-ratelimit-{}-{}", kind, name))?
+                .to_str()
+                .ok()?
+                .parse()
+                .ok()
+        };
+        Some(Self {
+            limit: header("limit")?,
+            remaining: header("remaining")?,
+            // reset is sent as epoch milliseconds
+            reset_at: header("reset").map(|ms| UNIX_EPOCH + Duration::from_millis(ms)),
+        })
+    }
+
+    // how long to wait so the remaining budget lasts until it resets
+    fn delay(&self, now: SystemTime) -> Duration {
+        if (self.remaining as f64) > self.limit as f64 * SLOWDOWN_THRESHOLD {
+            return Duration::ZERO;
+        }
+        let until_reset = self
+            .reset_at
+            .and_then(|reset_at| reset_at.duration_since(now).ok())
+            .unwrap_or_default();
+        (until_reset / (self.remaining as u32 + 1)).min(MAX_BACKOFF)
+    }
+}
+
+#[derive(Debug, Default)]
+struct RateLimit {
+    requests: Option<Budget>,
+    complexity: Option<Budget>,
+}
+
+impl RateLimit {
+    fn update(&mut self, headers: &HeaderMap) {
+        if let Some(requests) = Budget::from_headers(headers, "requests") {
+            self.requests = Some(requests);
+        }
+        if let Some(complexity) = Budget::from_headers(headers, "complexity") {
+            self.complexity = Some(complexity);
+        }
+    }
+
+    fn delay(&self, now: SystemTime) -> Duration {
+        [self.requests, self.complexity]
+            .iter()
+            .flatten()
+            .map(|budget| budget.delay(now))
+            .max()
+            .unwrap_or_default()
+    }
+
+    // how long to back off after being rate limited
+    fn until_reset(&self, now: SystemTime) -> Option<Duration> {
+        [self.requests, self.complexity]
+            .iter()
+            .flatten()
+            .filter(|budget| budget.remaining == 0)
+            .filter_map(|budget| budget.reset_at?.duration_since(now).ok())
+            .max()
+    }
+}
+
+fn backoff(attempt: u32) -> Duration {
+    (BASE_BACKOFF * 2u32.pow(attempt)).min(MAX_BACKOFF)
+}
+
+// how long to wait before retrying, Linear's hint is capped like our own backoff
+fn retry_delay(wait: Option<Duration>, attempt: u32) -> Duration {
+    wait.unwrap_or_else(|| backoff(attempt)).min(MAX_BACKOFF)
+}
+
+// whether the operation sent in a request body is a mutation, which isn't safe to repeat
+fn is_mutation(query: &str, operation_name: &str) -> bool {
+    query.split("mutation").skip(1).any(|rest| {
+        rest.trim_start()
+            .strip_prefix(operation_name)
+            .is_some_and(|rest| !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+    })
+}
+
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let seconds = headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(seconds))
+}
 
 #[derive(Debug, Default)]
 pub struct LinearClient {
     endpoint: String,
     client: Client,
+    rate_limit: Mutex<RateLimit>,
+    max_retries: u32,
 }
 
 impl LinearClient {
-    pub fn new(api_key: String) -> Result<Self, Box<dyn Error>> {
-        let endpoint = String::from("https://api.linear.app/graphql");
+    pub fn new(api_key: String, endpoint: String) -> Result<Self, ApiError> {
+        Self::build(api_key, endpoint, None, MAX_RETRIES)
+    }
+
+    // for callers that can't wait on Linear, like the commit hook: every request gives up
+    // after `timeout` and nothing is retried
+    pub fn without_retries(
+        api_key: String,
+        endpoint: String,
+        timeout: Duration,
+    ) -> Result<Self, ApiError> {
+        Self::build(api_key, endpoint, Some(timeout), 0)
+    }
 
+    fn build(
+        api_key: String,
+        endpoint: String,
+        timeout: Option<Duration>,
+        max_retries: u32,
+    ) -> Result<Self, ApiError> {
         let pkgver = env!("CARGO_PKG_VERSION");
-        let client = Client::builder()
+        let mut builder = Client::builder()
             .user_agent(format!("lt/{}", pkgver))
             .default_headers(
                 std::iter::once((
                     reqwest::header::AUTHORIZATION,
-                    reqwest::header::HeaderValue::from_str(&api_key.to_string()).unwrap(),
+                    reqwest::header::HeaderValue::from_str(&api_key)
+                        .map_err(|_| ApiError::InvalidToken)?,
                 ))
                 .collect(),
-            )
-            .build()?;
+            );
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
 
         Ok(Self {
-            client,
+            client: builder.build()?,
             endpoint,
+            rate_limit: Mutex::default(),
+            max_retries,
         })
     }
 
-    pub async fn query<T: GraphQLQuery>(&self, _query: T, variables: T::Variables) -> Result<T::ResponseData, Box<dyn Error>> {
+    pub async fn query<T: GraphQLQuery>(
+        &self,
+        _query: T,
+        variables: T::Variables,
+    ) -> Result<T::ResponseData, ApiError> {
         let var = T::build_query(variables);
-        let res = self.client.post(&self.endpoint).json(&var).send().await?;
-        let response_body: Response<T::ResponseData> = res.json().await?;
-        match response_body.data {
-            Some(t) => Ok(t),
-            None => {
-                match response_body.errors {
-                    None => Err("No idea what happened".into()),
-                    Some(e) => Err(e[0].message.clone().into())
+        let idempotent = !is_mutation(var.query, var.operation_name);
+        let mut attempt = 0;
+        loop {
+            let throttle = self.rate_limit.lock().unwrap().delay(SystemTime::now());
+            if !throttle.is_zero() {
+                tokio::time::sleep(throttle).await;
+            }
+
+            let response = self.client.post(&self.endpoint).json(&var).send().await;
+            // `unprocessed` is set when Linear can't have run the request, so even a mutation
+            // is safe to send again
+            let (error, wait, unprocessed) = match response {
+                Ok(res) => {
+                    let headers = res.headers().clone();
+                    self.rate_limit.lock().unwrap().update(&headers);
+                    let status = res.status();
+                    if status == StatusCode::TOO_MANY_REQUESTS {
+                        (ApiError::RateLimited, retry_after(&headers), true)
+                    } else if status.is_server_error() {
+                        let error = ApiError::Status {
+                            status,
+                            body: res.text().await.unwrap_or_default(),
+                        };
+                        (error, retry_after(&headers), false)
+                    } else {
+                        // Linear reports GraphQL errors with a 400, so the body is still worth reading
+                        let body = res.text().await?;
+                        let response_body: Response<T::ResponseData> =
+                            match serde_json::from_str(&body) {
+                                Ok(response_body) => response_body,
+                                Err(_) if !status.is_success() => {
+                                    return Err(ApiError::Status { status, body });
+                                }
+                                Err(e) => return Err(ApiError::Decode(e)),
+                            };
+                        let errors = response_body.errors.filter(|errors| !errors.is_empty());
+                        match (response_body.data, errors) {
+                            // rate limited requests are rejected before they run
+                            (_, Some(errors)) if ApiError::is_rate_limited(&errors) => {
+                                let wait = self
+                                    .rate_limit
+                                    .lock()
+                                    .unwrap()
+                                    .until_reset(SystemTime::now());
+                                (ApiError::RateLimited, wait, true)
+                            }
+                            // partial data would hide whatever failed, so the errors win
+                            (_, Some(errors)) => return Err(ApiError::GraphQL(errors)),
+                            (Some(data), None) => return Ok(data),
+                            (None, None) => return Err(ApiError::MissingData),
+                        }
+                    }
                 }
+                Err(e) if e.is_connect() => (ApiError::Http(e), None, true),
+                Err(e) if e.is_timeout() || e.is_request() => (ApiError::Http(e), None, false),
+                Err(e) => return Err(ApiError::Http(e)),
+            };
+
+            if attempt >= self.max_retries || !(idempotent || unprocessed) {
+                return Err(error);
             }
+            tokio::time::sleep(retry_delay(wait, attempt)).await;
+            attempt += 1;
         }
     }
 }
 
-/*#[cfg(test)]
-#[tokio::test]
+#[cfg(test)]
 mod tests {
+    use reqwest::header::{HeaderMap, HeaderValue};
+
     use super::*;
-    use crate::queries::*;
-    
+    use crate::{
+        queries::{
+            IssueQuery, NotificationArchiveMutation, issue_query, notification_archive_mutation,
+        },
+        stub_server::StubServer,
+    };
+
     #[test]
-    async fn hey() -> Option<()> {
-        let linear_api_token =
-            std::env::var("LINEAR_API_TOKEN").expect("Missing LINEAR_API_TOKEN env var");
-        let client = LinearClient::new(linear_api_token).unwrap();
-        let variables = example_query::Variables {};
-        
-        println!("Oka");
-        let thi = client.query(ExampleQuery, variables).await?;
-        println!("Oka2");
-        if let Ok(v) = thi {
-            println!("Okay!");
-        }
-        Some(())
+    fn test_rate_limit_delay() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000);
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-ratelimit-requests-limit",
+            HeaderValue::from_static("1500"),
+        );
+        headers.insert(
+            "x-ratelimit-requests-remaining",
+            HeaderValue::from_static("1000"),
+        );
+        headers.insert(
+            "x-ratelimit-requests-reset",
+            HeaderValue::from_static("1010000"),
+        );
 
-        /*if let Ok(v) = client.query(ExampleQuery, variables) {
-            assert_eq!(v.user_settings.id, "ad6eb6f5-4a44-440f-94aa-d3a9adcd0be9");
-            for node in v.issues.nodes {
-                assert!(!node.title.is_empty());
-                println!("Call again: {:?}", node.title);
-            }
-        }*/
+        let mut rate_limit = RateLimit::default();
+        rate_limit.update(&headers);
+        assert_eq!(rate_limit.delay(now), Duration::ZERO);
+
+        // 9 requests left for the next 10 seconds
+        headers.insert(
+            "x-ratelimit-requests-remaining",
+            HeaderValue::from_static("9"),
+        );
+        rate_limit.update(&headers);
+        assert_eq!(rate_limit.delay(now), Duration::from_secs(1));
+        assert_eq!(rate_limit.until_reset(now), None);
+
+        headers.insert(
+            "x-ratelimit-requests-remaining",
+            HeaderValue::from_static("0"),
+        );
+        rate_limit.update(&headers);
+        assert_eq!(rate_limit.until_reset(now), Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn test_graphql_errors() {
+        let errors: Vec<graphql_client::Error> = serde_json::from_str(
+            r#"[
+                {"message": "Entity not found", "path": ["issue"], "extensions": {"code": "INPUT_ERROR"}},
+                {"message": "Rate limit exceeded", "extensions": {"code": "RATELIMITED"}},
+                {"message": "Team not found in workspace"}
+            ]"#,
+        )
+        .unwrap();
+        assert!(ApiError::is_rate_limited(&errors));
+        assert!(!ApiError::is_rate_limited(&errors[..1]));
+        assert!(ApiError::GraphQL(errors[..1].to_vec()).is_not_found());
+        // only the code counts, not the wording
+        assert!(!ApiError::GraphQL(errors[1..].to_vec()).is_not_found());
+        assert_eq!(
+            ApiError::GraphQL(errors).to_string(),
+            "Entity not found; Rate limit exceeded; Team not found in workspace"
+        );
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(retry_delay(None, 0), BASE_BACKOFF);
+        assert_eq!(retry_delay(None, 2), BASE_BACKOFF * 4);
+        assert_eq!(retry_delay(None, 10), MAX_BACKOFF);
+        assert_eq!(retry_delay(Some(Duration::from_secs(3600)), 0), MAX_BACKOFF);
+        assert_eq!(retry_delay(Some(Duration::ZERO), 2), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_is_mutation() {
+        let query = "query IssueQuery($id: String!) { issue(id: $id) { id } }\n\
+                     mutation IssueUpdateMutation($id: String!) { issueUpdate(id: $id) { success } }\n\
+                     mutation IssueArchive { issueArchive { success } }";
+        assert!(!is_mutation(query, "IssueQuery"));
+        assert!(is_mutation(query, "IssueUpdateMutation"));
+        assert!(is_mutation(query, "IssueArchive"));
+        assert!(!is_mutation(query, "IssueUpdate"));
+    }
+
+    #[tokio::test]
+    async fn test_query_retries() {
+        let server = StubServer::start();
+        let client = server.client();
+        let variables = || issue_query::Variables {
+            id: String::from("LT-1"),
+        };
+
+        server.fail_next(2, 503);
+        assert!(client.query(IssueQuery, variables()).await.is_ok());
+        assert_eq!(server.operations().len(), 3);
+
+        // gives up after MAX_RETRIES retries
+        server.fail_next(10, 429);
+        assert!(matches!(
+            client.query(IssueQuery, variables()).await,
+            Err(ApiError::RateLimited)
+        ));
+        assert_eq!(server.operations().len(), 3 + 1 + MAX_RETRIES as usize);
     }
-}*/
+
+    #[tokio::test]
+    async fn test_mutation_retries() {
+        let server = StubServer::start();
+        let client = server.client();
+        let variables = || notification_archive_mutation::Variables {
+            id: String::from("notification-1"),
+        };
+
+        // a server error may come after the mutation ran, so it isn't sent again
+        server.fail_next(1, 503);
+        assert!(matches!(
+            client.query(NotificationArchiveMutation, variables()).await,
+            Err(ApiError::Status { .. })
+        ));
+        assert_eq!(server.operations().len(), 1);
+
+        // a rate limited request never ran
+        server.fail_next(1, 429);
+        assert!(
+            client
+                .query(NotificationArchiveMutation, variables())
+                .await
+                .is_ok()
+        );
+        assert_eq!(server.operations().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_partial_errors() {
+        let server = StubServer::start();
+        let fixture = std::fs::read_to_string(
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/IssueQuery.json"),
+        )
+        .unwrap();
+        let mut body: serde_json::Value = serde_json::from_str(&fixture).unwrap();
+        body["errors"] = serde_json::json!([{"message": "Cannot query the assignee"}]);
+        server.respond_with("IssueQuery", &body.to_string());
+        let result = server
+            .client()
+            .query(
+                IssueQuery,
+                issue_query::Variables {
+                    id: String::from("LT-1"),
+                },
+            )
+            .await;
+        assert_eq!(result.unwrap_err().to_string(), "Cannot query the assignee");
+    }
+}
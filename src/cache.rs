@@ -0,0 +1,282 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{
+        Mutex,
+        atomic::{AtomicU32, Ordering},
+    },
+};
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{IssueFragment, ordering::ViewOrdering, queries::custom_views_query::ViewFragment};
+
+// bump whenever the shape of a cached entry changes so older caches are ignored
+const CACHE_VERSION: u32 = 3;
+
+const ISSUES_FILE: &str = "issues.json";
+const VIEWS_FILE: &str = "views.json";
+const ORDERINGS_FILE: &str = "orderings.json";
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CachedIssues {
+    pub version: u32,
+    #[serde(rename = "fetchedAt")]
+    pub fetched_at: String,
+    pub issues: Vec<IssueFragment>,
+    #[serde(rename = "hasNextPage")]
+    pub has_next_page: bool,
+    #[serde(rename = "endCursor")]
+    pub end_cursor: Option<String>,
+}
+
+impl CachedIssues {
+    pub fn new(issues: Vec<IssueFragment>) -> Self {
+        Self {
+            version: CACHE_VERSION,
+            fetched_at: chrono::Utc::now().to_rfc3339(),
+            issues,
+            has_next_page: false,
+            end_cursor: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CachedViews {
+    pub version: u32,
+    #[serde(rename = "fetchedAt")]
+    pub fetched_at: String,
+    pub views: Vec<ViewFragment>,
+}
+
+impl CachedViews {
+    pub fn new(views: Vec<ViewFragment>) -> Self {
+        Self {
+            version: CACHE_VERSION,
+            fetched_at: chrono::Utc::now().to_rfc3339(),
+            views,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct CachedOrderings {
+    version: u32,
+    orderings: HashMap<String, ViewOrdering>,
+}
+
+// the newest save of each file, by the order the saves were asked for
+static GENERATIONS: Mutex<BTreeMap<&str, u64>> = Mutex::new(BTreeMap::new());
+
+fn next_generation(file: &'static str) -> u64 {
+    let mut generations = GENERATIONS.lock().unwrap();
+    let generation = generations.entry(file).or_default();
+    *generation += 1;
+    *generation
+}
+
+// $XDG_CACHE_HOME/lt, falling back to ~/.cache/lt. Tests never touch the real cache.
+fn cache_dir() -> Option<PathBuf> {
+    if cfg!(test) {
+        return None;
+    }
+    std::env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::home_dir().map(|home| home.join(".cache")))
+        .map(|dir| dir.join("lt"))
+}
+
+fn read<T: DeserializeOwned>(dir: &Path, file: &str) -> Option<T> {
+    let contents = fs::read_to_string(dir.join(file)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+// writes to a temporary file first so a crash never leaves a half written cache behind. Every
+// write gets its own temporary file, so concurrent writes, even from two running instances,
+// never write into the same one. A write that was overtaken by a newer save of the same file
+// is dropped rather than renamed over it.
+fn write<T: Serialize>(
+    dir: &Path,
+    file: &'static str,
+    value: &T,
+    generation: u64,
+) -> io::Result<()> {
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+    fs::create_dir_all(dir)?;
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let tmp = dir.join(format!("{}.{}.{}.tmp", file, std::process::id(), count));
+    let written = fs::File::create(&tmp)
+        .and_then(|mut f| f.write_all(serde_json::to_string(value)?.as_bytes()))
+        .and_then(|_| {
+            let generations = GENERATIONS.lock().unwrap();
+            if generations
+                .get(file)
+                .is_some_and(|newest| *newest > generation)
+            {
+                fs::remove_file(&tmp)
+            } else {
+                fs::rename(&tmp, dir.join(file))
+            }
+        });
+    if written.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    written
+}
+
+// the callers run on the async runtime, so the file is written on the blocking thread pool.
+// The cache is only a speedup, a failed write just means a slower next start.
+fn save<T: Serialize + Send + 'static>(file: &'static str, value: T) {
+    let Some(dir) = cache_dir() else {
+        return;
+    };
+    let generation = next_generation(file);
+    tokio::task::spawn_blocking(move || {
+        let _ = write(&dir, file, &value, generation);
+    });
+}
+
+// entries are checked one by one, so a stale or unreadable entry doesn't throw away the rest
+fn is_current(entry: &serde_json::Value) -> bool {
+    entry.get("version").and_then(|version| version.as_u64()) == Some(CACHE_VERSION.into())
+}
+
+fn load_issues_from(dir: &Path) -> HashMap<String, CachedIssues> {
+    let entries: HashMap<String, serde_json::Value> = read(dir, ISSUES_FILE).unwrap_or_default();
+    entries
+        .into_iter()
+        .filter(|(_, entry)| is_current(entry))
+        .filter_map(|(view_id, entry)| Some((view_id, serde_json::from_value(entry).ok()?)))
+        .collect()
+}
+
+fn load_current<T: DeserializeOwned>(dir: &Path, file: &str) -> Option<T> {
+    let entry: serde_json::Value = read(dir, file)?;
+    if !is_current(&entry) {
+        return None;
+    }
+    serde_json::from_value(entry).ok()
+}
+
+fn load_views_from(dir: &Path) -> Option<CachedViews> {
+    load_current(dir, VIEWS_FILE)
+}
+
+fn load_orderings_from(dir: &Path) -> HashMap<String, ViewOrdering> {
+    load_current(dir, ORDERINGS_FILE)
+        .map(|cached: CachedOrderings| cached.orderings)
+        .unwrap_or_default()
+}
+
+/// Cached issues per view id, skipping entries written by an incompatible version of `lt`.
+pub fn load_issues() -> HashMap<String, CachedIssues> {
+    cache_dir()
+        .map(|dir| load_issues_from(&dir))
+        .unwrap_or_default()
+}
+
+pub fn save_issues(issues: HashMap<String, CachedIssues>) {
+    save(ISSUES_FILE, issues)
+}
+
+pub fn load_views() -> Option<CachedViews> {
+    load_views_from(&cache_dir()?)
+}
+
+pub fn save_views(views: CachedViews) {
+    save(VIEWS_FILE, views)
+}
+
+/// The sort and grouping picked for each view id.
+pub fn load_orderings() -> HashMap<String, ViewOrdering> {
+    cache_dir()
+        .map(|dir| load_orderings_from(&dir))
+        .unwrap_or_default()
+}
+
+pub fn save_orderings(orderings: HashMap<String, ViewOrdering>) {
+    let cached = CachedOrderings {
+        version: CACHE_VERSION,
+        orderings,
+    };
+    save(ORDERINGS_FILE, cached)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{ordering::SortKey, widgets::make_issue};
+
+    use super::*;
+
+    #[test]
+    fn test_round_trip_skips_old_versions() {
+        let dir = std::env::temp_dir().join(format!("lt-cache-test-{}", std::process::id()));
+        let mut stale = CachedIssues::new(vec![make_issue("Old", "TEST-0")]);
+        stale.version = CACHE_VERSION + 1;
+        let issues = HashMap::from([
+            (
+                String::from("my_issues"),
+                CachedIssues::new(vec![make_issue("Ticket One", "TEST-1")]),
+            ),
+            (String::from("stale_view"), stale),
+        ]);
+        write(&dir, ISSUES_FILE, &issues, next_generation(ISSUES_FILE)).unwrap();
+        let views = CachedViews::new(vec![]);
+        write(&dir, VIEWS_FILE, &views, next_generation(VIEWS_FILE)).unwrap();
+
+        let loaded = load_issues_from(&dir);
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded["my_issues"].issues[0].identifier, "TEST-1");
+        assert!(load_views_from(&dir).is_some());
+        // only the cache files are left, no temporary ones
+        let mut files: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        files.sort();
+        assert_eq!(files, [ISSUES_FILE, VIEWS_FILE]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+    #[test]
+    fn test_orderings() {
+        let dir = std::env::temp_dir().join(format!("lt-orderings-test-{}", std::process::id()));
+        let ordering = |sort| ViewOrdering {
+            sort,
+            ..Default::default()
+        };
+        let older = next_generation(ORDERINGS_FILE);
+        let newer = next_generation(ORDERINGS_FILE);
+        let cached = |sort| CachedOrderings {
+            version: CACHE_VERSION,
+            orderings: HashMap::from([(String::from("my_issues"), ordering(sort))]),
+        };
+        write(&dir, ORDERINGS_FILE, &cached(SortKey::Priority), newer).unwrap();
+        // the older save finishing last doesn't put its orderings back
+        write(&dir, ORDERINGS_FILE, &cached(SortKey::Created), older).unwrap();
+        assert_eq!(
+            load_orderings_from(&dir)["my_issues"].sort,
+            SortKey::Priority
+        );
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        // orderings from before they were versioned are ignored
+        let unversioned = HashMap::from([(String::from("my_issues"), ordering(SortKey::Created))]);
+        write(
+            &dir,
+            ORDERINGS_FILE,
+            &unversioned,
+            next_generation(ORDERINGS_FILE),
+        )
+        .unwrap();
+        assert!(load_orderings_from(&dir).is_empty());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,539 @@
+// Non-interactive subcommands for scripts and shell prompts. Without a subcommand `lt`
+// starts the TUI.
+use std::{fs, io::Write, path::PathBuf, time::Duration};
+
+use crate::{
+    IssueFragment,
+    api::{ApiError, LinearClient},
+    git,
+    queries::{
+        CustomViewQuery, CustomViewsQuery, IssueQuery, MyIssuesQuery, SearchQuery,
+        custom_view_query, custom_views_query, issue_query, my_issues_query, search_query,
+    },
+};
+
+pub const USAGE: &str = "Usage:
+  lt                              start the TUI
+  lt list [--view NAME] [--json]  list my issues, or the issues in a custom view
+  lt show IDENTIFIER [--json]     show one issue, e.g. ENG-123
+  lt search TERM [--json]         search all issues
+  lt hook prepare-commit-msg FILE as a git hook, adds a \"Fixes ID\" trailer for the branch's issue";
+
+// exit codes, so scripts can tell a bad invocation from a failed request
+pub const EXIT_API_ERROR: i32 = 1;
+pub const EXIT_USAGE: i32 = 2;
+pub const EXIT_NOT_FOUND: i32 = 3;
+
+// the commit hook gives up on Linear after this long rather than hold up the commit
+pub const HOOK_TIMEOUT: Duration = Duration::from_secs(2);
+
+const PAGE_SIZE: i64 = 50;
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Help,
+    List {
+        view: Option<String>,
+        json: bool,
+    },
+    Show {
+        identifier: String,
+        json: bool,
+    },
+    Search {
+        term: String,
+        json: bool,
+    },
+    // git passes the message file, then where the message came from
+    PrepareCommitMsg {
+        message_file: PathBuf,
+        source: Option<String>,
+    },
+}
+
+enum CliError {
+    Api(ApiError),
+    NotFound(String),
+}
+
+impl From<ApiError> for CliError {
+    fn from(e: ApiError) -> Self {
+        CliError::Api(e)
+    }
+}
+
+// None means no subcommand was given and the TUI should start
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Option<Command>, String> {
+    let mut args = args.into_iter();
+    let Some(subcommand) = args.next() else {
+        return Ok(None);
+    };
+    let mut json = false;
+    let mut view = None;
+    let mut positional = vec![];
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--view" => view = Some(args.next().ok_or("--view needs a view name")?),
+            flag if flag.starts_with("--") => return Err(format!("unknown option {}", flag)),
+            _ => positional.push(arg),
+        }
+    }
+
+    let command = match subcommand.as_str() {
+        "help" | "--help" | "-h" => Command::Help,
+        "list" if positional.is_empty() => Command::List { view, json },
+        "show" if positional.len() == 1 && view.is_none() => Command::Show {
+            identifier: positional.remove(0),
+            json,
+        },
+        "search" if !positional.is_empty() && view.is_none() => Command::Search {
+            term: positional.join(" "),
+            json,
+        },
+        "hook"
+            if (2..=4).contains(&positional.len())
+                && positional[0] == "prepare-commit-msg"
+                && view.is_none()
+                && !json =>
+        {
+            Command::PrepareCommitMsg {
+                message_file: PathBuf::from(&positional[1]),
+                source: positional.get(2).cloned(),
+            }
+        }
+        "list" | "show" | "search" | "hook" => {
+            return Err(format!("wrong arguments for `lt {}`", subcommand));
+        }
+        other => return Err(format!("unknown command `{}`", other)),
+    };
+    Ok(Some(command))
+}
+
+// runs a subcommand, printing results to `out` and errors to stderr. Returns the exit code.
+pub async fn run(command: Command, client: &LinearClient, out: &mut impl Write) -> i32 {
+    let result = match command {
+        Command::Help => writeln!(out, "{}", USAGE).map_err(|e| e.to_string()),
+        Command::List { view, json } => match list(client, view).await {
+            Ok(issues) => print_issues(out, &issues, json),
+            Err(e) => return report(e),
+        },
+        Command::Show { identifier, json } => match show(client, identifier).await {
+            Ok(issue) => print_issue(out, &issue, json),
+            Err(e) => return report(e),
+        },
+        Command::Search { term, json } => match search(client, term).await {
+            Ok(issues) => print_issues(out, &issues, json),
+            Err(e) => return report(e),
+        },
+        Command::PrepareCommitMsg {
+            message_file,
+            source,
+        } => {
+            // merges and squashes come with a message of their own
+            if matches!(source.as_deref(), Some("merge" | "squash")) {
+                return 0;
+            }
+            let dir = std::env::current_dir().unwrap_or_default();
+            let branch = git::current_branch(&dir);
+            // a failed lookup mustn't stop the commit
+            if let Err(e) = prepare_commit_msg(client, branch, message_file).await {
+                eprintln!("lt: no issue trailer added: {}", e);
+            }
+            return 0;
+        }
+    };
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("lt: {}", e);
+            EXIT_API_ERROR
+        }
+    }
+}
+
+fn report(e: CliError) -> i32 {
+    match e {
+        CliError::Api(e) => {
+            eprintln!("lt: {}", e);
+            EXIT_API_ERROR
+        }
+        CliError::NotFound(what) => {
+            eprintln!("lt: {} not found", what);
+            EXIT_NOT_FOUND
+        }
+    }
+}
+
+async fn list(client: &LinearClient, view: Option<String>) -> Result<Vec<IssueFragment>, CliError> {
+    let Some(view) = view else {
+        return Ok(my_issues(client).await?);
+    };
+    let views = client
+        .query(CustomViewsQuery, custom_views_query::Variables {})
+        .await?;
+    let view = views
+        .custom_views
+        .nodes
+        .into_iter()
+        .find(|custom_view| {
+            custom_view.name.eq_ignore_ascii_case(&view)
+                || custom_view.id == view
+                || custom_view.slug_id.as_deref() == Some(view.as_str())
+        })
+        .ok_or_else(|| CliError::NotFound(format!("view `{}`", view)))?;
+    Ok(custom_view(client, view.id).await?)
+}
+
+// scripts want the whole list, so every page is fetched
+async fn my_issues(client: &LinearClient) -> Result<Vec<IssueFragment>, ApiError> {
+    let mut issues = vec![];
+    let mut after = None;
+    loop {
+        let variables = my_issues_query::Variables {
+            first: PAGE_SIZE,
+            after,
+        };
+        let data = client.query(MyIssuesQuery, variables).await?;
+        issues.extend(data.issues.nodes.into_iter().map(IssueFragment::from));
+        if !data.issues.page_info.has_next_page {
+            return Ok(issues);
+        }
+        after = data.issues.page_info.end_cursor;
+    }
+}
+
+async fn custom_view(
+    client: &LinearClient,
+    custom_view_id: String,
+) -> Result<Vec<IssueFragment>, ApiError> {
+    let mut issues = vec![];
+    let mut after = None;
+    loop {
+        let variables = custom_view_query::Variables {
+            custom_view_id: custom_view_id.clone(),
+            first: PAGE_SIZE,
+            after,
+        };
+        let data = client.query(CustomViewQuery, variables).await?;
+        let page = data.custom_view.issues;
+        issues.extend(page.nodes.into_iter().map(IssueFragment::from));
+        if !page.page_info.has_next_page {
+            return Ok(issues);
+        }
+        after = page.page_info.end_cursor;
+    }
+}
+
+async fn show(client: &LinearClient, identifier: String) -> Result<IssueFragment, CliError> {
+    let variables = issue_query::Variables {
+        id: identifier.clone(),
+    };
+    match client.query(IssueQuery, variables).await {
+        Ok(data) => Ok(data.issue.into()),
+        Err(e) if e.is_not_found() => Err(CliError::NotFound(format!("issue {}", identifier))),
+        Err(e) => Err(e.into()),
+    }
+}
+
+// search results are ranked, so only the first page is worth printing
+async fn search(client: &LinearClient, term: String) -> Result<Vec<IssueFragment>, CliError> {
+    let variables = search_query::Variables {
+        term,
+        first: PAGE_SIZE,
+        after: None,
+    };
+    let data = client.query(SearchQuery, variables).await?;
+    Ok(data
+        .search_issues
+        .nodes
+        .into_iter()
+        .map(IssueFragment::from)
+        .collect())
+}
+
+async fn prepare_commit_msg(
+    client: &LinearClient,
+    branch: Option<String>,
+    message_file: PathBuf,
+) -> Result<(), String> {
+    let Some(identifier) = branch.as_deref().and_then(git::identifier_from_branch) else {
+        return Ok(());
+    };
+    let message = fs::read_to_string(&message_file).map_err(|e| e.to_string())?;
+    if has_trailer(&message, &identifier) {
+        return Ok(());
+    }
+    let issue = match show(client, identifier).await {
+        Ok(issue) => issue,
+        // branches that only look like they name an issue
+        Err(CliError::NotFound(_)) => return Ok(()),
+        Err(CliError::Api(e)) => return Err(e.to_string()),
+    };
+    let trailer = format!("Fixes {} ({})", issue.identifier, issue.title);
+    fs::write(&message_file, add_trailer(&message, &trailer)).map_err(|e| e.to_string())
+}
+
+fn has_trailer(message: &str, identifier: &str) -> bool {
+    let trailer = format!("fixes {}", identifier.to_lowercase());
+    // LT-5 mustn't match a trailer for LT-50
+    message.lines().any(|line| {
+        line.to_lowercase()
+            .strip_prefix(&trailer)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t', '(']))
+    })
+}
+
+// the trailer goes after the message, above the comments git adds for the editor
+fn add_trailer(message: &str, trailer: &str) -> String {
+    let comments_start = message
+        .match_indices('\n')
+        .map(|(index, _)| index + 1)
+        .chain([0])
+        .filter(|index| message[*index..].starts_with('#'))
+        .min()
+        .unwrap_or(message.len());
+    let (body, comments) = message.split_at(comments_start);
+    let body = body.trim_end();
+    let comments = if comments.is_empty() {
+        String::new()
+    } else {
+        format!("\n{}", comments)
+    };
+    format!("{}\n\n{}\n{}", body, trailer, comments)
+}
+
+fn print_issues(out: &mut impl Write, issues: &[IssueFragment], json: bool) -> Result<(), String> {
+    if json {
+        let json = serde_json::to_string_pretty(issues).map_err(|e| e.to_string())?;
+        return writeln!(out, "{}", json).map_err(|e| e.to_string());
+    }
+    write!(out, "{}", issue_table(issues)).map_err(|e| e.to_string())
+}
+
+fn print_issue(out: &mut impl Write, issue: &IssueFragment, json: bool) -> Result<(), String> {
+    if json {
+        let json = serde_json::to_string_pretty(issue).map_err(|e| e.to_string())?;
+        return writeln!(out, "{}", json).map_err(|e| e.to_string());
+    }
+    write!(out, "{}", issue_details(issue)).map_err(|e| e.to_string())
+}
+
+fn issue_table(issues: &[IssueFragment]) -> String {
+    let width = |column: fn(&IssueFragment) -> &str| {
+        issues
+            .iter()
+            .map(|issue| column(issue).chars().count())
+            .max()
+            .unwrap_or(0)
+    };
+    let identifier_width = width(|issue| &issue.identifier);
+    let state_width = width(|issue| &issue.state.name);
+    let priority_width = width(|issue| &issue.priority_label);
+    issues
+        .iter()
+        .map(|issue| {
+            format!(
+                "{:identifier_width$}  {:state_width$}  {:priority_width$}  {}\n",
+                issue.identifier, issue.state.name, issue.priority_label, issue.title
+            )
+        })
+        .collect()
+}
+
+fn issue_details(issue: &IssueFragment) -> String {
+    let labels: Vec<&str> = issue
+        .labels
+        .edges
+        .iter()
+        .map(|edge| edge.node.name.as_str())
+        .collect();
+    let mut details = format!("{} {}\n\n", issue.identifier, issue.title);
+    let fields = [
+        ("State", Some(issue.state.name.clone())),
+        ("Priority", Some(issue.priority_label.clone())),
+        (
+            "Assignee",
+            issue
+                .assignee
+                .as_ref()
+                .map(|assignee| assignee.display_name.clone()),
+        ),
+        (
+            "Project",
+            issue.project.as_ref().map(|project| project.name.clone()),
+        ),
+        ("Labels", (!labels.is_empty()).then(|| labels.join(", "))),
+        ("Branch", Some(issue.branch_name.clone())),
+        ("URL", Some(issue.url.clone())),
+    ];
+    for (name, value) in fields {
+        if let Some(value) = value {
+            details.push_str(&format!("{:10}{}\n", format!("{}:", name), value));
+        }
+    }
+    if let Some(description) = issue.description.as_ref().filter(|d| !d.is_empty()) {
+        details.push_str(&format!("\n{}\n", description));
+    }
+    details
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{stub_server::StubServer, widgets::make_issue};
+
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(args(&[])), Ok(None));
+        assert_eq!(
+            parse(args(&["list", "--view", "Bugs", "--json"])),
+            Ok(Some(Command::List {
+                view: Some(String::from("Bugs")),
+                json: true
+            }))
+        );
+        assert_eq!(
+            parse(args(&["search", "login", "bug"])),
+            Ok(Some(Command::Search {
+                term: String::from("login bug"),
+                json: false
+            }))
+        );
+        assert_eq!(
+            parse(args(&[
+                "hook",
+                "prepare-commit-msg",
+                ".git/COMMIT_EDITMSG",
+                "message"
+            ])),
+            Ok(Some(Command::PrepareCommitMsg {
+                message_file: PathBuf::from(".git/COMMIT_EDITMSG"),
+                source: Some(String::from("message"))
+            }))
+        );
+        assert!(parse(args(&["hook", "pre-push", "origin"])).is_err());
+        assert!(parse(args(&["show"])).is_err());
+        assert!(parse(args(&["list", "--verbose"])).is_err());
+        assert!(parse(args(&["frobnicate"])).is_err());
+    }
+
+    #[test]
+    fn test_issue_table() {
+        let mut issue = make_issue("Ticket One", "TEST-1");
+        issue.state.name = String::from("In Progress");
+        issue.priority_label = String::from("High");
+        let table = issue_table(&[issue, make_issue("Ticket Two", "TEST-22")]);
+        insta::assert_snapshot!(table);
+    }
+
+    #[test]
+    fn test_add_trailer() {
+        let trailer = "Fixes LT-5 (Write migration)";
+        assert_eq!(
+            add_trailer("Add the table\n", trailer),
+            "Add the table\n\nFixes LT-5 (Write migration)\n"
+        );
+        // an empty message keeps its first line free for the subject
+        assert_eq!(
+            add_trailer("\n# Please enter the commit message\n", trailer),
+            "\n\nFixes LT-5 (Write migration)\n\n# Please enter the commit message\n"
+        );
+        assert!(has_trailer("Add the table\n\nfixes lt-5\n", "LT-5"));
+        assert!(!has_trailer("LT-5: Add the table\n", "LT-5"));
+        assert!(has_trailer("Add the table\n\nFixes LT-5 (Add)\n", "LT-5"));
+        assert!(!has_trailer("Add the table\n\nFixes LT-50\n", "LT-5"));
+    }
+
+    #[tokio::test]
+    async fn test_prepare_commit_msg() {
+        let server = StubServer::start();
+        let client = server.client();
+        let message_file =
+            std::env::temp_dir().join(format!("lt-COMMIT_EDITMSG-{}", std::process::id()));
+
+        fs::write(&message_file, "Add the table\n").unwrap();
+        let branch = Some(String::from("lt-5-write-migration"));
+        prepare_commit_msg(&client, branch.clone(), message_file.clone())
+            .await
+            .unwrap();
+        let message = fs::read_to_string(&message_file).unwrap();
+        assert_eq!(message, "Add the table\n\nFixes LT-5 (Write migration)\n");
+        // the trailer is only added once
+        prepare_commit_msg(&client, branch, message_file.clone())
+            .await
+            .unwrap();
+        assert_eq!(fs::read_to_string(&message_file).unwrap(), message);
+        assert_eq!(server.operations(), vec!["IssueQuery"]);
+
+        fs::write(&message_file, "Add the table\n").unwrap();
+        prepare_commit_msg(&client, Some(String::from("main")), message_file.clone())
+            .await
+            .unwrap();
+        prepare_commit_msg(&client, None, message_file.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(&message_file).unwrap(),
+            "Add the table\n"
+        );
+
+        // the hook's client doesn't retry, the commit goes ahead without a trailer
+        let client = LinearClient::without_retries(
+            String::from("test-token"),
+            server.endpoint.clone(),
+            HOOK_TIMEOUT,
+        )
+        .unwrap();
+        server.fail_next(1, 503);
+        let branch = Some(String::from("lt-5-write-migration"));
+        assert!(
+            prepare_commit_msg(&client, branch, message_file.clone())
+                .await
+                .is_err()
+        );
+        assert_eq!(server.operations(), vec!["IssueQuery", "IssueQuery"]);
+        assert_eq!(
+            fs::read_to_string(&message_file).unwrap(),
+            "Add the table\n"
+        );
+        fs::remove_file(&message_file).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_run() {
+        let server = StubServer::start();
+        let client = server.client();
+
+        let mut out = vec![];
+        let command = Command::List {
+            view: None,
+            json: true,
+        };
+        assert_eq!(run(command, &client, &mut out).await, 0);
+        let issues: Vec<IssueFragment> = serde_json::from_slice(&out).unwrap();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].identifier, "LT-1");
+
+        let command = Command::List {
+            view: Some(String::from("Nope")),
+            json: false,
+        };
+        assert_eq!(run(command, &client, &mut vec![]).await, EXIT_NOT_FOUND);
+
+        server.respond_with(
+            "SearchQuery",
+            r#"{"errors": [{"message": "Authentication required"}]}"#,
+        );
+        let command = Command::Search {
+            term: String::from("login"),
+            json: false,
+        };
+        assert_eq!(run(command, &client, &mut vec![]).await, EXIT_API_ERROR);
+    }
+}
@@ -0,0 +1,603 @@
+// User configuration, read from $XDG_CONFIG_HOME/lt/config.toml (or ~/.config/lt/config.toml):
+//
+//   startup_tab = "Bugs"      # "My Issues" or the name of a custom view
+//   refresh_interval = 120    # seconds between background refreshes, 0 turns them off
+//   icons = "ascii"           # "nerdfont", "unicode", "ascii" or "auto"
+//   worktree_dir = "~/src/worktrees"  # where worktrees for issue branches are created
+//
+//   [layout]
+//   list_width = 30           # percentage of the screen used by the issue list
+//
+//   [keys]
+//   down = ["j", "ctrl-n"]    # one key or a list of keys per action
+use std::{fmt, path::PathBuf, time::Duration};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use toml_edit::{DocumentMut, Item, Value};
+
+use crate::iconmap::IconSet;
+
+const CONFIG_FILE: &str = "config.toml";
+const DEFAULT_LIST_WIDTH: u16 = 25;
+const LIST_WIDTH_RANGE: std::ops::RangeInclusive<i64> = 10..=90;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    Down,
+    Up,
+    ScrollDown,
+    ScrollUp,
+    OpenUrl,
+    CopyBranch,
+    Search,
+    Filter,
+    Sort,
+    Group,
+    NewIssue,
+    ChangeState,
+    ToggleComments,
+    Reply,
+    EditDescription,
+    Parent,
+    NextSubIssue,
+    PrevSubIssue,
+    OpenSubIssue,
+    ExpandSubIssue,
+    Relations,
+    Checkout,
+    Worktree,
+    MarkRead,
+    Archive,
+    Retry,
+    NextTab,
+    PrevTab,
+    Board,
+    ColumnLeft,
+    ColumnRight,
+    MoveCardLeft,
+    MoveCardRight,
+    Select,
+}
+
+impl Action {
+    const ALL: [Action; 35] = [
+        Action::Quit,
+        Action::Down,
+        Action::Up,
+        Action::ScrollDown,
+        Action::ScrollUp,
+        Action::OpenUrl,
+        Action::CopyBranch,
+        Action::Search,
+        Action::Filter,
+        Action::Sort,
+        Action::Group,
+        Action::NewIssue,
+        Action::ChangeState,
+        Action::ToggleComments,
+        Action::Reply,
+        Action::EditDescription,
+        Action::Parent,
+        Action::NextSubIssue,
+        Action::PrevSubIssue,
+        Action::OpenSubIssue,
+        Action::ExpandSubIssue,
+        Action::Relations,
+        Action::Checkout,
+        Action::Worktree,
+        Action::MarkRead,
+        Action::Archive,
+        Action::Retry,
+        Action::NextTab,
+        Action::PrevTab,
+        Action::Board,
+        Action::ColumnLeft,
+        Action::ColumnRight,
+        Action::MoveCardLeft,
+        Action::MoveCardRight,
+        Action::Select,
+    ];
+
+    // the name used in the [keys] table
+    fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Down => "down",
+            Action::Up => "up",
+            Action::ScrollDown => "scroll_down",
+            Action::ScrollUp => "scroll_up",
+            Action::OpenUrl => "open",
+            Action::CopyBranch => "copy_branch",
+            Action::Search => "search",
+            Action::Filter => "filter",
+            Action::Sort => "sort",
+            Action::Group => "group",
+            Action::NewIssue => "new_issue",
+            Action::ChangeState => "change_state",
+            Action::ToggleComments => "toggle_comments",
+            Action::Reply => "reply",
+            Action::EditDescription => "edit_description",
+            Action::Parent => "parent",
+            Action::NextSubIssue => "next_sub_issue",
+            Action::PrevSubIssue => "prev_sub_issue",
+            Action::OpenSubIssue => "open_sub_issue",
+            Action::ExpandSubIssue => "expand_sub_issue",
+            Action::Relations => "relations",
+            Action::Checkout => "checkout",
+            Action::Worktree => "worktree",
+            Action::MarkRead => "mark_read",
+            Action::Archive => "archive",
+            Action::Retry => "retry",
+            Action::NextTab => "next_tab",
+            Action::PrevTab => "prev_tab",
+            Action::Board => "board",
+            Action::ColumnLeft => "column_left",
+            Action::ColumnRight => "column_right",
+            Action::MoveCardLeft => "move_card_left",
+            Action::MoveCardRight => "move_card_right",
+            Action::Select => "select",
+        }
+    }
+
+    fn default_keys(&self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q", "esc"],
+            Action::Down => &["j"],
+            Action::Up => &["k"],
+            Action::ScrollDown => &["down"],
+            Action::ScrollUp => &["up"],
+            Action::OpenUrl => &["o"],
+            Action::CopyBranch => &["y", "c"],
+            Action::Search => &["/"],
+            Action::Filter => &["f"],
+            Action::Sort => &["S"],
+            Action::Group => &["G"],
+            Action::NewIssue => &["n"],
+            Action::ChangeState => &["s"],
+            Action::ToggleComments => &["t"],
+            Action::Reply => &["r"],
+            Action::EditDescription => &["e"],
+            Action::Parent => &["p"],
+            Action::NextSubIssue => &["]"],
+            Action::PrevSubIssue => &["["],
+            Action::OpenSubIssue => &["g"],
+            Action::ExpandSubIssue => &["space"],
+            Action::Relations => &["m"],
+            Action::Checkout => &["B"],
+            Action::Worktree => &["W"],
+            Action::MarkRead => &["x"],
+            Action::Archive => &["a"],
+            Action::Retry => &["R"],
+            Action::NextTab => &["tab"],
+            Action::PrevTab => &["backtab"],
+            Action::Board => &["b"],
+            Action::ColumnLeft => &["h", "left"],
+            Action::ColumnRight => &["l", "right"],
+            Action::MoveCardLeft => &["H"],
+            Action::MoveCardRight => &["L"],
+            Action::Select => &["enter"],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    code: KeyCode,
+    ctrl: bool,
+}
+
+impl Key {
+    fn parse(spec: &str) -> Option<Self> {
+        let (ctrl, name) = match spec.strip_prefix("ctrl-") {
+            Some(name) => (true, name),
+            None => (false, spec),
+        };
+        let mut chars = name.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match name.to_lowercase().as_str() {
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "esc" => KeyCode::Esc,
+                "enter" => KeyCode::Enter,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                f => KeyCode::F(
+                    f.strip_prefix('f')?
+                        .parse()
+                        .ok()
+                        .filter(|n| (1..=12).contains(n))?,
+                ),
+            },
+        };
+        Some(Self { code, ctrl })
+    }
+
+    fn matches(&self, key: &KeyEvent) -> bool {
+        self.code == key.code && self.ctrl == key.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    // whether pressing this key could also mean typing text
+    fn is_text(&self) -> bool {
+        matches!(self.code, KeyCode::Char(_)) && !self.ctrl
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "ctrl-")?;
+        }
+        match self.code {
+            // a fraction slash keeps the hint from reading like a path
+            KeyCode::Char('/') => write!(f, "⁄"),
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::BackTab => write!(f, "shift-tab"),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            code => write!(f, "{}", code.to_string().to_lowercase()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    bindings: Vec<(Action, Vec<Key>)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: Action::ALL
+                .iter()
+                .map(|action| {
+                    let keys = action
+                        .default_keys()
+                        .iter()
+                        .filter_map(|spec| Key::parse(spec));
+                    (*action, keys.collect())
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Keymap {
+    pub fn action(&self, key: &KeyEvent) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(_, keys)| keys.iter().any(|k| k.matches(key)))
+            .map(|(action, _)| *action)
+    }
+
+    // like `action`, but ignores keys that type text, for when an input has focus
+    pub fn non_text_action(&self, key: &KeyEvent) -> Option<Action> {
+        let action = self.action(key)?;
+        let key = self.keys(action).iter().find(|k| k.matches(key))?;
+        (!key.is_text()).then_some(action)
+    }
+
+    fn keys(&self, action: Action) -> &[Key] {
+        self.bindings
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, keys)| keys.as_slice())
+            .unwrap_or_default()
+    }
+
+    // label for key hints, e.g. "y" for copy_branch
+    pub fn label(&self, action: Action) -> String {
+        self.keys(action)
+            .first()
+            .map(|key| key.to_string())
+            .unwrap_or_else(|| String::from("unbound"))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub keymap: Keymap,
+    // percentage of the screen used by the issue list, the rest shows the selected issue
+    pub list_width: u16,
+    pub startup_tab: Option<String>,
+    // None turns background refreshes off
+    pub refresh_interval: Option<Duration>,
+    // None picks an icon set based on the terminal
+    pub icons: Option<IconSet>,
+    // None puts worktrees next to the repo
+    pub worktree_dir: Option<PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            keymap: Keymap::default(),
+            list_width: DEFAULT_LIST_WIDTH,
+            startup_tab: None,
+            refresh_interval: None,
+            icons: None,
+            worktree_dir: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ConfigError {
+    pub path: PathBuf,
+    pub problems: Vec<String>,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid config in {}:", self.path.display())?;
+        for problem in &self.problems {
+            write!(f, "\n  - {}", problem)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+// a leading ~ stands for the home directory, as it would in a shell
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+// $XDG_CONFIG_HOME/lt, falling back to ~/.config/lt
+pub fn config_path() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::home_dir().map(|home| home.join(".config")))
+        .map(|dir| dir.join("lt").join(CONFIG_FILE))
+}
+
+/// Reads the config file, falling back to the defaults when there isn't one.
+pub fn load() -> Result<Config, ConfigError> {
+    let Some(path) = config_path() else {
+        return Ok(Config::default());
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => Config::parse(&contents).map_err(|problems| ConfigError { path, problems }),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(e) => Err(ConfigError {
+            path,
+            problems: vec![e.to_string()],
+        }),
+    }
+}
+
+impl Config {
+    // every problem is collected, so they can all be fixed in one go
+    pub fn parse(contents: &str) -> Result<Self, Vec<String>> {
+        let document: DocumentMut = contents.parse().map_err(|e| vec![format!("{}", e)])?;
+        let mut config = Config::default();
+        let mut problems = vec![];
+
+        for (name, item) in document.iter() {
+            match name {
+                "startup_tab" => match item.as_str() {
+                    Some(tab) => config.startup_tab = Some(tab.to_string()),
+                    None => problems.push(String::from("`startup_tab` must be a string")),
+                },
+                "refresh_interval" => match item.as_integer() {
+                    Some(0) => config.refresh_interval = None,
+                    Some(seconds) if seconds > 0 => {
+                        config.refresh_interval = Some(Duration::from_secs(seconds as u64))
+                    }
+                    _ => problems.push(String::from(
+                        "`refresh_interval` must be a number of seconds (0 turns it off)",
+                    )),
+                },
+                "icons" => match item.as_str() {
+                    Some("auto") => config.icons = None,
+                    Some(name) if IconSet::from_name(name).is_some() => {
+                        config.icons = IconSet::from_name(name)
+                    }
+                    _ => problems.push(String::from(
+                        "`icons` must be one of \"nerdfont\", \"unicode\", \"ascii\" or \"auto\"",
+                    )),
+                },
+                "worktree_dir" => match item.as_str() {
+                    Some(dir) => config.worktree_dir = Some(expand_home(dir)),
+                    None => problems.push(String::from("`worktree_dir` must be a path")),
+                },
+                "layout" => config.parse_layout(item, &mut problems),
+                "keys" => config.parse_keys(item, &mut problems),
+                other => problems.push(format!("unknown setting `{}`", other)),
+            }
+        }
+
+        problems.extend(config.conflicts());
+        if problems.is_empty() {
+            Ok(config)
+        } else {
+            Err(problems)
+        }
+    }
+
+    fn parse_layout(&mut self, item: &Item, problems: &mut Vec<String>) {
+        let Some(layout) = item.as_table_like() else {
+            problems.push(String::from("`layout` must be a table"));
+            return;
+        };
+        for (name, item) in layout.iter() {
+            match name {
+                "list_width" => match item.as_integer() {
+                    Some(width) if LIST_WIDTH_RANGE.contains(&width) => {
+                        self.list_width = width as u16
+                    }
+                    _ => problems.push(format!(
+                        "`layout.list_width` must be a number between {} and {}",
+                        LIST_WIDTH_RANGE.start(),
+                        LIST_WIDTH_RANGE.end()
+                    )),
+                },
+                other => problems.push(format!("unknown setting `layout.{}`", other)),
+            }
+        }
+    }
+
+    fn parse_keys(&mut self, item: &Item, problems: &mut Vec<String>) {
+        let Some(keys) = item.as_table_like() else {
+            problems.push(String::from("`keys` must be a table"));
+            return;
+        };
+        for (name, item) in keys.iter() {
+            let Some(action) = Action::ALL.iter().find(|action| action.name() == name) else {
+                let names: Vec<&str> = Action::ALL.iter().map(|action| action.name()).collect();
+                problems.push(format!(
+                    "unknown action `keys.{}`, expected one of: {}",
+                    name,
+                    names.join(", ")
+                ));
+                continue;
+            };
+            let specs: Vec<&Value> = match item.as_value() {
+                Some(Value::Array(array)) => array.iter().collect(),
+                Some(value) => vec![value],
+                None => vec![],
+            };
+            let mut bound = vec![];
+            for spec in specs {
+                match spec.as_str().and_then(Key::parse) {
+                    Some(key) => bound.push(key),
+                    None => problems.push(format!(
+                        "`keys.{}`: {} is not a key, use e.g. \"j\", \"ctrl-d\", \"enter\" or \"tab\"",
+                        name,
+                        spec.to_string().trim()
+                    )),
+                }
+            }
+            if let Some((_, keys)) = self.keymap.bindings.iter_mut().find(|(a, _)| a == action) {
+                *keys = bound;
+            }
+        }
+    }
+
+    fn conflicts(&self) -> Vec<String> {
+        let mut conflicts = vec![];
+        let bindings = &self.keymap.bindings;
+        for (i, (action, keys)) in bindings.iter().enumerate() {
+            for key in keys {
+                for (other, other_keys) in &bindings[i + 1..] {
+                    if other_keys.contains(key) {
+                        conflicts.push(format!(
+                            "`{}` is bound to both `{}` and `{}`",
+                            key,
+                            action.name(),
+                            other.name()
+                        ));
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyEventKind, KeyEventState};
+
+    use super::*;
+
+    fn key_event(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::empty(),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(
+            r#"
+            startup_tab = "Bugs"
+            refresh_interval = 120
+            icons = "ascii"
+            worktree_dir = "/src/worktrees"
+
+            [layout]
+            list_width = 40
+
+            [keys]
+            down = ["ctrl-n", "J"]
+            open = "O"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.startup_tab.as_deref(), Some("Bugs"));
+        assert_eq!(config.refresh_interval, Some(Duration::from_secs(120)));
+        assert_eq!(config.list_width, 40);
+        assert_eq!(config.icons, Some(IconSet::Ascii));
+        assert_eq!(config.worktree_dir, Some(PathBuf::from("/src/worktrees")));
+
+        let keymap = config.keymap;
+        let ctrl_n = key_event(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        assert_eq!(keymap.action(&ctrl_n), Some(Action::Down));
+        assert_eq!(keymap.non_text_action(&ctrl_n), Some(Action::Down));
+        // plain n still creates an issue, j is unbound now
+        let n = key_event(KeyCode::Char('n'), KeyModifiers::NONE);
+        assert_eq!(keymap.action(&n), Some(Action::NewIssue));
+        assert_eq!(keymap.non_text_action(&n), None);
+        assert_eq!(
+            keymap.action(&key_event(KeyCode::Char('j'), KeyModifiers::NONE)),
+            None
+        );
+        assert_eq!(keymap.label(Action::Down), "ctrl-n");
+        assert_eq!(keymap.label(Action::ScrollUp), "↑");
+    }
+
+    #[test]
+    fn test_parse_reports_every_problem() {
+        let problems = Config::parse(
+            r#"
+            theme = "dark"
+
+            [layout]
+            list_width = 95
+
+            [keys]
+            jump = "g"
+            up = "ctrl-shift-k"
+            quit = "j"
+            "#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            problems,
+            [
+                "unknown setting `theme`",
+                "`layout.list_width` must be a number between 10 and 90",
+                "unknown action `keys.jump`, expected one of: quit, down, up, scroll_down, \
+                 scroll_up, open, copy_branch, search, filter, sort, group, new_issue, \
+                 change_state, toggle_comments, reply, edit_description, parent, \
+                 next_sub_issue, prev_sub_issue, open_sub_issue, expand_sub_issue, relations, \
+                 checkout, worktree, mark_read, archive, retry, next_tab, prev_tab, board, \
+                 column_left, column_right, move_card_left, move_card_right, select",
+                "`keys.up`: \"ctrl-shift-k\" is not a key, use e.g. \"j\", \"ctrl-d\", \"enter\" or \"tab\"",
+                "`j` is bound to both `quit` and `down`",
+            ]
+        );
+    }
+}
@@ -0,0 +1,156 @@
+// Edits text in the user's editor, for descriptions that are too long for an input field. The
+// terminal has to be handed over to the editor while it runs.
+
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::{
+    env,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::Command,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+// $VISUAL wins over $EDITOR, as it does for git
+fn editor_command() -> String {
+    env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .ok()
+        .filter(|command| !command.trim().is_empty())
+        .unwrap_or_else(|| String::from("vi"))
+}
+
+// returns the edited text, or None when it wasn't changed
+pub fn edit(text: &str, name: &str) -> io::Result<Option<String>> {
+    edit_with(&editor_command(), text, name)
+}
+
+// a fresh file that only the user can read, so nobody else can see the text or swap the file
+// out before the editor opens it
+fn create_temp_file(name: &str) -> io::Result<(PathBuf, File)> {
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+    loop {
+        let path = env::temp_dir().join(format!(
+            "lt-{}-{}-{}.md",
+            name,
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        match options.open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+// keeps text that couldn't be saved in a file that outlives lt, and returns its path
+pub fn keep(text: &str, name: &str) -> io::Result<PathBuf> {
+    let (path, mut file) = create_temp_file(name)?;
+    match file.write_all(format!("{}\n", text).as_bytes()) {
+        Ok(()) => Ok(path),
+        Err(e) => {
+            let _ = fs::remove_file(&path);
+            Err(e)
+        }
+    }
+}
+
+fn edit_with(command: &str, text: &str, name: &str) -> io::Result<Option<String>> {
+    let (path, mut file) = create_temp_file(name)?;
+    let written = file.write_all(text.as_bytes());
+    drop(file);
+    let edited = written
+        .and_then(|()| run(command, &path))
+        .and_then(|()| fs::read_to_string(&path));
+    let _ = fs::remove_file(&path);
+    // most editors add a final newline
+    let edited = edited?.trim_end().to_string();
+    Ok((edited != text.trim_end()).then_some(edited))
+}
+
+// the command goes through the shell like git does it, so it may carry arguments and quotes,
+// like "code --wait"
+fn run(command: &str, path: &Path) -> io::Result<()> {
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", command))
+        .arg(command)
+        .arg(path)
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "{} exited with {}",
+            command, status
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::{create_temp_file, edit_with, keep};
+
+    #[test]
+    fn test_edit() {
+        assert_eq!(edit_with("true", "Some text", "TEST-1").unwrap(), None);
+
+        // `cp` stands in for an editor that replaces the file
+        let edited = env::temp_dir().join(format!("lt-edited-{}.md", std::process::id()));
+        fs::write(&edited, "Some other text\n").unwrap();
+        let command = format!("cp {}", edited.display());
+        assert_eq!(
+            edit_with(&command, "Some text", "TEST-1").unwrap(),
+            Some(String::from("Some other text"))
+        );
+        // a final newline alone doesn't count as a change
+        assert_eq!(
+            edit_with(&command, "Some other text", "TEST-1").unwrap(),
+            None
+        );
+        fs::remove_file(&edited).unwrap();
+
+        let e = edit_with("false", "Some text", "TEST-1").unwrap_err();
+        assert!(e.to_string().starts_with("false exited with"));
+
+        // the shell takes care of quoting
+        let edited = env::temp_dir().join(format!("lt edited {}.md", std::process::id()));
+        fs::write(&edited, "Quoted\n").unwrap();
+        let command = format!("cp '{}'", edited.display());
+        assert_eq!(
+            edit_with(&command, "Some text", "TEST-1").unwrap(),
+            Some(String::from("Quoted"))
+        );
+        fs::remove_file(&edited).unwrap();
+    }
+
+    #[test]
+    fn test_keep() {
+        let path = keep("Some text", "TEST-1").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "Some text\n");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_create_temp_file() {
+        let (first, _) = create_temp_file("TEST-1").unwrap();
+        let (second, _) = create_temp_file("TEST-1").unwrap();
+        assert_ne!(first, second);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&first).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        fs::remove_file(&first).unwrap();
+        fs::remove_file(&second).unwrap();
+    }
+}
@@ -0,0 +1,77 @@
+// Fuzzy matching for the local filter. Every character of the pattern has to appear in the text
+// in order; runs of consecutive characters and matches at the start of words score higher.
+
+const MATCH_SCORE: i64 = 16;
+const CONSECUTIVE_BONUS: i64 = 12;
+const WORD_START_BONUS: i64 = 8;
+const GAP_PENALTY: i64 = 1;
+
+fn same_char(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+fn is_word_start(text: &[char], index: usize) -> bool {
+    index == 0 || !text[index - 1].is_alphanumeric()
+}
+
+// matches `pattern` against `text` starting with its first character at `start`
+fn match_from(pattern: &[char], text: &[char], start: usize) -> Option<(i64, Vec<usize>)> {
+    let mut positions = Vec::with_capacity(pattern.len());
+    let mut score = 0;
+    let mut next = start;
+    for &p in pattern {
+        let index = (next..text.len()).find(|&i| same_char(text[i], p))?;
+        score += MATCH_SCORE;
+        if is_word_start(text, index) {
+            score += WORD_START_BONUS;
+        }
+        match positions.last() {
+            Some(&last) if last + 1 == index => score += CONSECUTIVE_BONUS,
+            Some(&last) => score -= GAP_PENALTY * (index - last - 1) as i64,
+            None => {}
+        }
+        positions.push(index);
+        next = index + 1;
+    }
+    Some((score, positions))
+}
+
+// the best score for `pattern` in `text` and the char indices it matched, or None without a match
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<(i64, Vec<usize>)> {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let first = *pattern.first()?;
+    // trying every occurrence of the first character finds tighter matches than the leftmost one
+    (0..text.len())
+        .filter(|&i| same_char(text[i], first))
+        .filter_map(|start| match_from(&pattern, &text, start))
+        .max_by_key(|(score, _)| *score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fuzzy_match;
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert_eq!(fuzzy_match("", "anything"), None);
+        assert_eq!(fuzzy_match("xyz", "Login page"), None);
+        assert_eq!(
+            fuzzy_match("lgn", "Login").map(|m| m.1),
+            Some(vec![0, 2, 4])
+        );
+        // the tight match at a word start wins over the leftmost one
+        assert_eq!(
+            fuzzy_match("pag", "a paper page").map(|m| m.1),
+            Some(vec![8, 9, 10])
+        );
+        assert_eq!(
+            fuzzy_match("ENG12", "eng-12").map(|m| m.1),
+            Some(vec![0, 1, 2, 4, 5])
+        );
+
+        let (consecutive, _) = fuzzy_match("bug", "bug report").unwrap();
+        let (scattered, _) = fuzzy_match("bug", "build tag").unwrap();
+        assert!(consecutive > scattered);
+    }
+}
@@ -0,0 +1,145 @@
+// Branches and worktrees for issues, made with the git command line in the repo `lt` runs in.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| format!("couldn't run git: {}", e))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+fn branch_exists(dir: &Path, branch: &str) -> bool {
+    let reference = format!("refs/heads/{}", branch);
+    git(dir, &["rev-parse", "--verify", "--quiet", &reference]).is_ok()
+}
+
+// None outside of a repo and on a detached HEAD
+pub fn current_branch(dir: &Path) -> Option<String> {
+    git(dir, &["branch", "--show-current"])
+        .ok()
+        .filter(|branch| !branch.is_empty())
+}
+
+// branches that weren't created from Linear often still start with the issue, like
+// "eng-123-fix-login" or "me/ENG-123"
+pub fn identifier_from_branch(branch: &str) -> Option<String> {
+    branch.split('/').find_map(|segment| {
+        let mut parts = segment.splitn(3, '-');
+        let (key, number) = (parts.next()?, parts.next()?);
+        let is_key = key.starts_with(|c: char| c.is_ascii_alphabetic())
+            && key.chars().all(|c| c.is_ascii_alphanumeric());
+        let is_number = !number.is_empty() && number.chars().all(|c| c.is_ascii_digit());
+        (is_key && is_number).then(|| format!("{}-{}", key.to_uppercase(), number))
+    })
+}
+
+// switches to the branch, creating it from HEAD first if needed. Returns whether it was created.
+pub fn checkout(dir: &Path, branch: &str) -> Result<bool, String> {
+    if branch_exists(dir, branch) {
+        git(dir, &["switch", branch])?;
+        Ok(false)
+    } else {
+        git(dir, &["switch", "-c", branch])?;
+        Ok(true)
+    }
+}
+
+// checks the branch out in a new worktree in `worktree_dir`, or by default in a
+// "<repo>-worktrees" directory next to the repo. Returns the worktree's path.
+pub fn add_worktree(
+    dir: &Path,
+    worktree_dir: Option<&Path>,
+    branch: &str,
+) -> Result<PathBuf, String> {
+    let root = PathBuf::from(git(dir, &["rev-parse", "--show-toplevel"])?);
+    let worktree_dir = match worktree_dir {
+        Some(worktree_dir) => worktree_dir.to_path_buf(),
+        None => {
+            let name = root.file_name().unwrap_or_default().to_string_lossy();
+            root.with_file_name(format!("{}-worktrees", name))
+        }
+    };
+    // branch names like "me/lt-5-fix-login" would otherwise nest directories
+    let path = worktree_dir.join(branch.replace('/', "-"));
+    let path_arg = path.to_string_lossy();
+    if branch_exists(dir, branch) {
+        git(dir, &["worktree", "add", &path_arg, branch])?;
+    } else {
+        git(dir, &["worktree", "add", "-b", branch, &path_arg])?;
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::{add_worktree, checkout, current_branch, git, identifier_from_branch};
+
+    #[test]
+    fn test_identifier_from_branch() {
+        assert_eq!(
+            identifier_from_branch("eng-123-fix-login").as_deref(),
+            Some("ENG-123")
+        );
+        assert_eq!(identifier_from_branch("me/ENG-7").as_deref(), Some("ENG-7"));
+        assert_eq!(identifier_from_branch("main"), None);
+        assert_eq!(identifier_from_branch("fix-login"), None);
+    }
+
+    #[test]
+    fn test_branches() {
+        let root = env::temp_dir().join(format!("lt-git-{}", std::process::id()));
+        let repo = root.join("repo");
+        fs::create_dir_all(&repo).unwrap();
+        git(&repo, &["init", "--quiet", "--initial-branch", "main"]).unwrap();
+        git(
+            &repo,
+            &[
+                "-c",
+                "user.name=lt",
+                "-c",
+                "user.email=lt@example.com",
+                "commit",
+                "--quiet",
+                "--allow-empty",
+                "--message",
+                "Initial commit",
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(current_branch(&repo).as_deref(), Some("main"));
+        assert_eq!(current_branch(&root), None);
+
+        assert_eq!(checkout(&repo, "lt-5-write-migration"), Ok(true));
+        assert_eq!(current_branch(&repo).unwrap(), "lt-5-write-migration");
+        checkout(&repo, "main").unwrap();
+        // an existing branch is switched to
+        assert_eq!(checkout(&repo, "lt-5-write-migration"), Ok(false));
+        assert_eq!(current_branch(&repo).unwrap(), "lt-5-write-migration");
+
+        let path = add_worktree(&repo, None, "me/lt-7-fix-login").unwrap();
+        assert_eq!(path, root.join("repo-worktrees").join("me-lt-7-fix-login"));
+        assert_eq!(current_branch(&path).unwrap(), "me/lt-7-fix-login");
+        let worktrees = root.join("worktrees");
+        checkout(&repo, "main").unwrap();
+        let path = add_worktree(&repo, Some(&worktrees), "lt-5-write-migration").unwrap();
+        assert_eq!(current_branch(&path).unwrap(), "lt-5-write-migration");
+        // a branch can only be checked out in one worktree
+        assert!(add_worktree(&repo, Some(&worktrees), "main").is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
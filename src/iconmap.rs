@@ -1,5 +1,74 @@
-// linear icon name to nerdfont icon
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IconSet {
+    #[default]
+    NerdFont,
+    Unicode,
+    Ascii,
+}
+
+// chosen once at startup, read by every widget that draws icons
+static ICON_SET: AtomicU8 = AtomicU8::new(IconSet::NerdFont as u8);
+
+impl IconSet {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "nerdfont" => Some(IconSet::NerdFont),
+            "unicode" => Some(IconSet::Unicode),
+            "ascii" => Some(IconSet::Ascii),
+            _ => None,
+        }
+    }
+
+    // Nerd Fonts can't be detected, so only fall back when the terminal clearly can't show them
+    pub fn detect() -> Self {
+        let var = |name: &str| std::env::var(name).unwrap_or_default();
+        let locale = [var("LC_ALL"), var("LC_CTYPE"), var("LANG")]
+            .into_iter()
+            .find(|value| !value.is_empty())
+            .unwrap_or_default();
+        Self::detect_from(&var("TERM"), &locale)
+    }
+
+    fn detect_from(term: &str, locale: &str) -> Self {
+        let utf8 = locale.to_uppercase().replace('-', "").contains("UTF8");
+        match term {
+            "linux" | "dumb" | "vt100" | "vt220" => IconSet::Ascii,
+            _ if !locale.is_empty() && !utf8 => IconSet::Ascii,
+            _ => IconSet::NerdFont,
+        }
+    }
+}
+
+pub fn set_icon_set(icon_set: IconSet) {
+    ICON_SET.store(icon_set as u8, Ordering::Relaxed);
+}
+
+fn icon_set() -> IconSet {
+    match ICON_SET.load(Ordering::Relaxed) {
+        1 => IconSet::Unicode,
+        2 => IconSet::Ascii,
+        _ => IconSet::NerdFont,
+    }
+}
+
+// linear icon name to an icon in the current icon set
 pub fn ico_to_nf(name: &str) -> String {
+    ico_in(icon_set(), name)
+}
+
+fn ico_in(icon_set: IconSet, name: &str) -> String {
+    match (icon_set, name) {
+        (IconSet::NerdFont, _) => {}
+        // without nerd fonts only the built in tabs get an icon
+        (IconSet::Unicode, "Home") => return String::from("⌂ "),
+        (IconSet::Unicode, "Magnify") => return String::from("⌕ "),
+        (IconSet::Unicode, "Inbox") => return String::from("✉ "),
+        (IconSet::Unicode, "Cube") => return String::from("◈ "),
+        (IconSet::Unicode, "Refresh") => return String::from("↻ "),
+        (IconSet::Unicode | IconSet::Ascii, _) => return String::new(),
+    }
     let nf = match name {
         "Umbrella" => "  ",
         "FaceStarEyes" => "  ",
@@ -246,6 +315,26 @@ pub fn ico_to_nf(name: &str) -> String {
 }
 
 pub fn p_to_nf(priority: f64) -> String {
+    p_in(icon_set(), priority)
+}
+
+fn p_in(icon_set: IconSet, priority: f64) -> String {
+    let icon = match (icon_set, priority) {
+        (IconSet::NerdFont, _) => None,
+        (IconSet::Unicode, 1.0) => Some("⚠  "),
+        (IconSet::Unicode, 2.0) => Some("▇  "),
+        (IconSet::Unicode, 3.0) => Some("▅  "),
+        (IconSet::Unicode, 4.0) => Some("▂  "),
+        (IconSet::Unicode, _) => Some("─  "),
+        (IconSet::Ascii, 1.0) => Some("[!!] "),
+        (IconSet::Ascii, 2.0) => Some("[hi] "),
+        (IconSet::Ascii, 3.0) => Some("[md] "),
+        (IconSet::Ascii, 4.0) => Some("[lo] "),
+        (IconSet::Ascii, _) => Some("[--] "),
+    };
+    if let Some(icon) = icon {
+        return icon.to_string();
+    }
     let nf = match priority {
         1.0 => "󰀧  ",
         2.0 => "  ",
@@ -257,6 +346,29 @@ pub fn p_to_nf(priority: f64) -> String {
 }
 
 pub fn state_to_nf(state: &str) -> String {
+    state_in(icon_set(), state)
+}
+
+fn state_in(icon_set: IconSet, state: &str) -> String {
+    let icon = match (icon_set, state) {
+        (IconSet::Unicode, "triage") => Some("◇ "),
+        (IconSet::Unicode, "backlog") => Some("◌ "),
+        (IconSet::Unicode, "unstarted") => Some("○ "),
+        (IconSet::Unicode, "started") => Some("◐ "),
+        (IconSet::Unicode, "completed") => Some("● "),
+        (IconSet::Unicode, "cancelled") => Some("⊘ "),
+        (IconSet::Ascii, "triage") => Some("[?] "),
+        (IconSet::Ascii, "backlog") => Some("[.] "),
+        (IconSet::Ascii, "unstarted") => Some("[ ] "),
+        (IconSet::Ascii, "started") => Some("[>] "),
+        (IconSet::Ascii, "completed") => Some("[x] "),
+        (IconSet::Ascii, "cancelled") => Some("[-] "),
+        (IconSet::Unicode | IconSet::Ascii, _) => Some(""),
+        (IconSet::NerdFont, _) => None,
+    };
+    if let Some(icon) = icon {
+        return icon.to_string();
+    }
     let nf = match state {
         "triage" => "󰿡 ",
         "backlog" => "󱥸 ",
@@ -271,7 +383,7 @@ pub fn state_to_nf(state: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use crate::iconmap::{ico_to_nf, p_to_nf, state_to_nf};
+    use crate::iconmap::{IconSet, ico_in, ico_to_nf, p_in, p_to_nf, state_in, state_to_nf};
 
     #[test]
     fn test_transitions() {
@@ -284,4 +396,27 @@ mod tests {
         assert_eq!( state_to_nf("unknown"), "");
     }
 
+    #[test]
+    fn test_icon_sets() {
+        assert_eq!(p_in(IconSet::Ascii, 1.0), "[!!] ");
+        assert_eq!(state_in(IconSet::Ascii, "started"), "[>] ");
+        assert_eq!(ico_in(IconSet::Ascii, "Umbrella"), "");
+        assert_eq!(p_in(IconSet::Unicode, 0.0), "─  ");
+        assert_eq!(state_in(IconSet::Unicode, "completed"), "● ");
+        assert_eq!(ico_in(IconSet::Unicode, "Home"), "⌂ ");
+        assert_eq!(ico_in(IconSet::Unicode, "Inbox"), "✉ ");
+        assert_eq!(state_in(IconSet::Unicode, "unknown"), "");
+
+        assert_eq!(
+            IconSet::detect_from("xterm-256color", "en_US.UTF-8"),
+            IconSet::NerdFont
+        );
+        assert_eq!(
+            IconSet::detect_from("xterm-256color", ""),
+            IconSet::NerdFont
+        );
+        assert_eq!(IconSet::detect_from("xterm-256color", "C"), IconSet::Ascii);
+        assert_eq!(IconSet::detect_from("linux", "en_US.utf8"), IconSet::Ascii);
+    }
+
 }
@@ -1,17 +1,33 @@
 mod api;
+mod cache;
+mod cli;
+mod config;
+mod editor;
+mod fuzzy;
+mod git;
 mod iconmap;
+mod ordering;
 mod queries;
+#[cfg(test)]
+mod stub_server;
 mod widgets;
 use crossterm::event::EventStream;
 use duplicate::duplicate_item;
 use serde::{Deserialize, Serialize};
-use widgets::{MyIssuesWidget, SelectedIssueWidget, TabWidget};
+use widgets::{
+    BoardWidget, CyclesWidget, InboxWidget, IssueFormWidget, MyIssuesWidget, ProjectsWidget,
+    RelationPickerWidget, SelectedIssueWidget, StatePickerWidget, TabWidget,
+};
 
 use std::{
     fmt::{self},
+    sync::Arc,
     time::Duration,
 };
 
+use api::LinearClient;
+use config::{Action, Config};
+
 use color_eyre::eyre::Result;
 
 use crossterm::event::{Event, KeyCode, KeyEventKind};
@@ -24,18 +40,72 @@ use tokio_stream::StreamExt;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    if std::env::var("LINEAR_API_TOKEN").is_err() {
+    let command = match cli::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("lt: {}\n\n{}", e, cli::USAGE);
+            std::process::exit(cli::EXIT_USAGE);
+        }
+    };
+    if command == Some(cli::Command::Help) {
+        println!("{}", cli::USAGE);
+        return Ok(());
+    }
+    let hook = matches!(command, Some(cli::Command::PrepareCommitMsg { .. }));
+    let Ok(linear_api_token) = std::env::var("LINEAR_API_TOKEN") else {
+        // the commit hook mustn't stop commits where no token is set
+        if hook {
+            return Ok(());
+        }
         println!("Hey! Set a LINEAR_API_TOKEN environment variable to get this show started.");
         std::process::exit(1);
+    };
+    install_hooks()?;
+    // LINEAR_API_ENDPOINT points lt at another server, e.g. a local stub
+    let endpoint = std::env::var("LINEAR_API_ENDPOINT")
+        .unwrap_or_else(|_| String::from(api::DEFAULT_ENDPOINT));
+    let client = if hook {
+        match LinearClient::without_retries(linear_api_token, endpoint, cli::HOOK_TIMEOUT) {
+            Ok(client) => client,
+            Err(_) => return Ok(()),
+        }
+    } else {
+        LinearClient::new(linear_api_token, endpoint)?
+    };
+    let client = Arc::new(client);
+
+    if let Some(command) = command {
+        let exit_code = cli::run(command, &client, &mut std::io::stdout()).await;
+        std::process::exit(exit_code);
     }
-    color_eyre::install()?;
+    let config = match config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("lt: {}", e);
+            std::process::exit(cli::EXIT_USAGE);
+        }
+    };
+
+    iconmap::set_icon_set(config.icons.unwrap_or_else(iconmap::IconSet::detect));
 
     let terminal = ratatui::init();
-    let app_result = App::new().run(terminal).await;
+    let app_result = App::new(client, config).run(terminal).await;
     ratatui::restore();
     app_result
 }
 
+// the terminal is restored before a panic is reported, so the report isn't printed in raw mode
+fn install_hooks() -> Result<()> {
+    let (panic_hook, eyre_hook) = color_eyre::config::HookBuilder::default().into_hooks();
+    eyre_hook.install()?;
+    let panic_hook = panic_hook.into_panic_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        ratatui::restore();
+        panic_hook(panic_info);
+    }));
+    Ok(())
+}
+
 #[derive(Clone, Debug, Default, PartialEq)]
 pub enum InputMode {
     #[default]
@@ -49,13 +119,31 @@ pub enum LtEvent<'a> {
     None,
     SelectIssue,
     SearchIssues(&'a str),
+    ChangeIssueState,
+    SetIssueState(String, workflow_states_query::WorkflowStateFragment),
+    NewIssue,
+    CreateIssue(Box<issue_create_mutation::IssueCreateInput>),
+    CreateRelation(widgets::NewRelation),
+    DeleteRelation(String, widgets::Relation),
+    EditDescription,
+    OverwriteDescription,
+    OpenProject(String),
+    OpenCycle(String),
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub enum TabChangeEvent {
     None,
     FetchCustomViewIssues(custom_views_query::ViewFragment),
     SearchIssues,
+    ShowInbox,
+    ShowProjects,
+    ShowCycles,
+    // the issues of a project picked in the projects tab, by project id
+    FetchProjectIssues(String),
+    // the issues of a cycle picked in the cycles tab, by cycle id
+    FetchCycleIssues(String),
+    #[default]
     FetchMyIssues,
 }
 
@@ -65,105 +153,462 @@ impl PartialEq for custom_views_query::ViewFragment {
     }
 }
 
-impl Default for TabChangeEvent {
-    fn default() -> Self {
-        Self::FetchMyIssues
-    }
-}
-
 //#[derive(Debug)]
 struct App {
     should_quit: bool,
+    config: Config,
     issue_list_widget: MyIssuesWidget,
     selected_issue_widget: SelectedIssueWidget,
     tab_widget: TabWidget,
+    state_picker_widget: StatePickerWidget,
+    issue_form_widget: IssueFormWidget,
+    relation_picker_widget: RelationPickerWidget,
+    board_widget: BoardWidget,
+    inbox_widget: InboxWidget,
+    projects_widget: ProjectsWidget,
+    cycles_widget: CyclesWidget,
+    // set when the description should be opened in the editor after this event
+    editing_description: bool,
 }
 
 impl App {
     const FRAMES_PER_SECOND: f32 = 30.0;
 
-    pub fn new() -> Self {
+    pub fn new(client: Arc<LinearClient>, config: Config) -> Self {
+        let keymap = Arc::new(config.keymap.clone());
+        let issue_list_widget = MyIssuesWidget::new(client.clone(), keymap.clone());
         Self {
             should_quit: false,
-            issue_list_widget: MyIssuesWidget::default(),
-            selected_issue_widget: SelectedIssueWidget::default(),
-            tab_widget: TabWidget::default(),
+            editing_description: false,
+            config,
+            selected_issue_widget: SelectedIssueWidget::new(client.clone(), keymap.clone()),
+            tab_widget: TabWidget::new(client.clone(), keymap.clone()),
+            state_picker_widget: StatePickerWidget::new(client.clone(), keymap.clone()),
+            issue_form_widget: IssueFormWidget::new(client.clone()),
+            relation_picker_widget: RelationPickerWidget::default(),
+            inbox_widget: InboxWidget::new(client.clone(), keymap.clone()),
+            projects_widget: ProjectsWidget::new(client.clone(), keymap.clone()),
+            cycles_widget: CyclesWidget::new(client.clone(), keymap.clone()),
+            board_widget: BoardWidget::new(client, keymap, issue_list_widget.clone()),
+            issue_list_widget,
         }
     }
 
     pub async fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
-        self.tab_widget.run();
-        self.issue_list_widget.run(TabChangeEvent::default());
+        let mut refresh = self.start();
         let period = Duration::from_secs_f32(1.0 / Self::FRAMES_PER_SECOND);
         let mut interval = tokio::time::interval(period);
         let mut events = EventStream::new();
 
         while !self.should_quit {
             tokio::select! {
-                _ = interval.tick() => { terminal.draw(|frame| self.draw(frame))?; },
+                _ = interval.tick() => {
+                    if let Some(tab_change) = self.tab_widget.take_tab_change() {
+                        self.change_tab(tab_change);
+                    }
+                    self.tab_widget.set_unread_count(self.inbox_widget.unread_count());
+                    self.issue_form_widget.reopen_if_failed();
+                    terminal.draw(|frame| self.draw(frame))?;
+                },
+                _ = async {
+                    match refresh.as_mut() {
+                        Some(refresh) => refresh.tick().await,
+                        None => std::future::pending().await,
+                    }
+                } => {
+                    self.issue_list_widget.refresh();
+                    self.inbox_widget.run();
+                },
                 Some(Ok(event)) = events.next() => {
                     self.handle_event(&event)
                 },
             }
+            if std::mem::take(&mut self.editing_description) {
+                // the event stream would otherwise read the keys meant for the editor
+                drop(events);
+                ratatui::restore();
+                self.edit_description();
+                terminal = ratatui::init();
+                events = EventStream::new();
+            }
         }
         Ok(())
     }
 
+    // kicks off the first fetches and returns the background refresh timer, if there is one
+    fn start(&mut self) -> Option<tokio::time::Interval> {
+        // draw whatever was cached last time right away, then refresh in the background
+        self.tab_widget.load_cache();
+        self.issue_list_widget.load_cache();
+        self.tab_widget.run();
+        // fetched up front for the unread badge
+        self.inbox_widget.run();
+        // started from a repo, the issue being worked on is the likeliest one to look up
+        if let Some(branch) = std::env::current_dir()
+            .ok()
+            .and_then(|dir| git::current_branch(&dir))
+        {
+            self.selected_issue_widget.open_branch_issue(branch);
+        }
+        let startup_tab = match &self.config.startup_tab {
+            Some(name) => self.tab_widget.select_startup_tab(name),
+            None => TabChangeEvent::default(),
+        };
+        self.change_tab(startup_tab);
+        self.config
+            .refresh_interval
+            .map(|period| tokio::time::interval_at(tokio::time::Instant::now() + period, period))
+    }
+
+    // the inbox, the projects and the cycles take the place of the issue list while their tab
+    // is selected
+    fn change_tab(&mut self, tab_change: TabChangeEvent) {
+        match tab_change {
+            TabChangeEvent::None => {}
+            TabChangeEvent::ShowInbox => {
+                self.board_widget.close();
+                self.projects_widget.close();
+                self.cycles_widget.close();
+                self.inbox_widget.open();
+                let selected_issue = self.inbox_widget.selected_issue();
+                self.selected_issue_widget
+                    .set_selected_issue(selected_issue);
+            }
+            TabChangeEvent::ShowProjects => {
+                self.board_widget.close();
+                self.inbox_widget.close();
+                self.cycles_widget.close();
+                self.projects_widget.open();
+            }
+            TabChangeEvent::ShowCycles => {
+                self.board_widget.close();
+                self.inbox_widget.close();
+                self.projects_widget.close();
+                self.cycles_widget.open();
+            }
+            tab_change => {
+                self.projects_widget.close();
+                self.cycles_widget.close();
+                if self.inbox_widget.visible() {
+                    self.inbox_widget.close();
+                    let selected_issue = self.issue_list_widget.selected_issue();
+                    self.selected_issue_widget
+                        .set_selected_issue(selected_issue);
+                }
+                self.issue_list_widget.run(tab_change);
+            }
+        }
+    }
+
+    // runs while the terminal is handed over to the editor
+    fn edit_description(&mut self) {
+        let Some(issue) = self.selected_issue_widget.selected_issue() else {
+            return;
+        };
+        let description = issue.description.clone().unwrap_or_default();
+        match editor::edit(&description, &issue.identifier) {
+            Ok(Some(description)) => self.issue_list_widget.update_description(
+                issue,
+                description,
+                false,
+                self.selected_issue_widget.clone(),
+            ),
+            Ok(None) => {}
+            Err(e) => self
+                .selected_issue_widget
+                .set_notice(format!("Couldn't run the editor: {}", e)),
+        }
+    }
+
+    // checks out the selected issue's branch here or in a new worktree, and offers to start the
+    // issue if it isn't yet
+    fn start_branch(&mut self, worktree: bool) {
+        let Some(issue) = self.selected_issue_widget.selected_issue() else {
+            return;
+        };
+        let dir = std::env::current_dir().unwrap_or_default();
+        let branch = &issue.branch_name;
+        let result = if worktree {
+            git::add_worktree(&dir, self.config.worktree_dir.as_deref(), branch)
+                .map(|path| format!("Checked out {} in {}", branch, path.display()))
+        } else {
+            git::checkout(&dir, branch).map(|created| {
+                if created {
+                    format!("Created and checked out {}", branch)
+                } else {
+                    format!("Switched to {}", branch)
+                }
+            })
+        };
+        let unstarted = ["triage", "backlog", "unstarted"].contains(&issue.state.type_.as_str());
+        match result {
+            Ok(message) if unstarted => self.state_picker_widget.offer_start(issue, message),
+            Ok(message) => self.selected_issue_widget.set_message(message),
+            Err(e) => self.selected_issue_widget.set_notice(format!("git: {}", e)),
+        }
+    }
+
+    fn open_relation_picker(&mut self) {
+        if let Some((issue, relations)) = self.selected_issue_widget.relations() {
+            self.relation_picker_widget.open(
+                issue,
+                relations,
+                self.issue_list_widget.current_issues(),
+            );
+        }
+    }
+
     fn draw(&mut self, frame: &mut Frame) {
         use Constraint::{Length, Min, Percentage};
         let vertical = Layout::vertical([Length(1), Min(0)]);
         let [tab_area, body_area] = vertical.areas(frame.area());
-        let horizontal = Layout::horizontal([Percentage(25), Percentage(75)]);
-        let [list_area, body_area] = horizontal.areas(body_area);
-        frame.render_widget(&self.issue_list_widget, list_area);
-        frame.render_widget(&self.selected_issue_widget, body_area);
+        if self.board_widget.visible {
+            frame.render_widget(&self.board_widget, body_area);
+        } else if self.projects_widget.visible() {
+            frame.render_widget(&self.projects_widget, body_area);
+        } else if self.cycles_widget.visible() {
+            frame.render_widget(&self.cycles_widget, body_area);
+        } else {
+            let list_width = self.config.list_width;
+            let horizontal =
+                Layout::horizontal([Percentage(list_width), Percentage(100 - list_width)]);
+            let [list_area, body_area] = horizontal.areas(body_area);
+            if self.inbox_widget.visible() {
+                frame.render_widget(&self.inbox_widget, list_area);
+            } else {
+                frame.render_widget(&self.issue_list_widget, list_area);
+            }
+            frame.render_widget(&self.selected_issue_widget, body_area);
+        }
         frame.render_widget(&self.tab_widget, tab_area);
+        frame.render_widget(&self.state_picker_widget, frame.area());
+        frame.render_widget(&self.issue_form_widget, frame.area());
+        frame.render_widget(&self.relation_picker_widget, frame.area());
     }
 
     fn handle_event(&mut self, event: &Event) {
-        if let Event::Key(key) = event {
-            if key.kind == KeyEventKind::Press {
-                match (key.code, self.issue_list_widget.input_mode.clone()) {
-                    (KeyCode::Char('q') | KeyCode::Esc, InputMode::Normal) => {
-                        self.should_quit = true;
+        if self.state_picker_widget.visible {
+            if let LtEvent::SetIssueState(issue_id, workflow_state) =
+                self.state_picker_widget.handle_event(event)
+            {
+                self.issue_list_widget.update_issue_state(
+                    &issue_id,
+                    workflow_state,
+                    self.selected_issue_widget.clone(),
+                );
+            }
+            return;
+        }
+        if self.relation_picker_widget.visible {
+            match self.relation_picker_widget.handle_event(event) {
+                LtEvent::CreateRelation(relation) => {
+                    self.issue_list_widget
+                        .create_relation(relation, self.selected_issue_widget.clone());
+                }
+                LtEvent::DeleteRelation(issue_id, relation) => {
+                    self.issue_list_widget.delete_relation(
+                        issue_id,
+                        relation,
+                        self.selected_issue_widget.clone(),
+                    );
+                }
+                _ => (),
+            }
+            return;
+        }
+        if self.selected_issue_widget.replying {
+            self.selected_issue_widget.handle_event(event);
+            return;
+        }
+        if self.selected_issue_widget.has_pending_description() {
+            if let LtEvent::OverwriteDescription = self.selected_issue_widget.handle_event(event)
+                && let Some(pending) = self.selected_issue_widget.take_pending_description()
+            {
+                self.issue_list_widget.update_description(
+                    pending.issue,
+                    pending.description,
+                    true,
+                    self.selected_issue_widget.clone(),
+                );
+            }
+            return;
+        }
+        if self.issue_form_widget.visible {
+            if let LtEvent::CreateIssue(input) = self.issue_form_widget.handle_event(event) {
+                self.issue_list_widget.create_issue(
+                    *input,
+                    self.selected_issue_widget.clone(),
+                    self.issue_form_widget.clone(),
+                );
+            }
+            return;
+        }
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            let input_mode = self.issue_list_widget.input_mode.clone();
+            let action = match input_mode {
+                InputMode::Normal => self.config.keymap.action(key),
+                // keys that type text belong to the search input
+                InputMode::Editing => self.config.keymap.non_text_action(key),
+            };
+            match (key.code, input_mode, action) {
+                (KeyCode::Esc, InputMode::Editing, _) => {
+                    self.issue_list_widget.toggle_search_mode();
+                    let selected_issue = self.issue_list_widget.selected_issue();
+                    self.selected_issue_widget
+                        .set_selected_issue(selected_issue);
+                }
+                // esc clears the filter and leaves the board before it quits
+                (KeyCode::Esc, InputMode::Normal, _) if self.issue_list_widget.filtering => {
+                    self.issue_list_widget.toggle_search_mode();
+                    let selected_issue = self.issue_list_widget.selected_issue();
+                    self.selected_issue_widget
+                        .set_selected_issue(selected_issue);
+                }
+                (KeyCode::Esc, InputMode::Normal, _) if self.board_widget.visible => {
+                    self.board_widget.close();
+                }
+                (KeyCode::Esc, InputMode::Normal, _) if self.projects_widget.in_project() => {
+                    self.projects_widget.back();
+                }
+                (KeyCode::Esc, InputMode::Normal, _) if self.cycles_widget.in_cycle() => {
+                    self.cycles_widget.back();
+                }
+                (_, InputMode::Normal, Some(Action::Quit)) => {
+                    self.should_quit = true;
+                }
+                (_, _, Some(Action::NextTab | Action::PrevTab)) => {
+                    let tab_change = self.tab_widget.handle_event(event);
+                    self.change_tab(tab_change);
+                    if self.issue_list_widget.show_search_input {
+                        self.issue_list_widget.toggle_search_mode();
                     }
-
-                    (KeyCode::Tab | KeyCode::BackTab, _) => {
-                        self.issue_list_widget
-                            .run(self.tab_widget.handle_event(event));
-                        if self.issue_list_widget.show_search_input {
-                            self.issue_list_widget.toggle_search_mode();
+                }
+                (_, InputMode::Normal, _) if self.inbox_widget.visible() => match action {
+                    Some(Action::Relations) => self.open_relation_picker(),
+                    Some(Action::Checkout) => self.start_branch(false),
+                    Some(Action::Worktree) => self.start_branch(true),
+                    _ => {
+                        if let LtEvent::EditDescription =
+                            self.selected_issue_widget.handle_event(event)
+                        {
+                            self.editing_description = true;
+                        }
+                        if let LtEvent::SelectIssue = self.inbox_widget.handle_event(event) {
+                            let selected_issue = self.inbox_widget.selected_issue();
+                            self.selected_issue_widget
+                                .set_selected_issue(selected_issue);
                         }
                     }
-                    (KeyCode::Char('/'), InputMode::Normal) => {
-                        self.issue_list_widget.toggle_search_mode();
+                },
+                (_, InputMode::Normal, _) if self.projects_widget.visible() => {
+                    if let LtEvent::OpenProject(project_id) =
+                        self.projects_widget.handle_event(event)
+                    {
+                        self.issue_list_widget
+                            .run(TabChangeEvent::FetchProjectIssues(project_id));
                     }
-                    (KeyCode::Esc, InputMode::Editing) => {
-                        self.issue_list_widget.toggle_search_mode();
+                }
+                (_, InputMode::Normal, _) if self.cycles_widget.visible() => {
+                    if let LtEvent::OpenCycle(cycle_id) = self.cycles_widget.handle_event(event) {
+                        self.issue_list_widget
+                            .run(TabChangeEvent::FetchCycleIssues(cycle_id));
                     }
-                    _ => {
-                        self.selected_issue_widget.handle_event(event);
-                        match self.issue_list_widget.handle_event(event) {
-                            LtEvent::SelectIssue => {
-                                let issue_list_widget_state =
-                                    self.issue_list_widget.state.write().unwrap();
-                                let selected_issue: Option<IssueFragment> =
-                                    issue_list_widget_state.list_state.selected().map(|index| {
-                                        issue_list_widget_state.issue_map
-                                            [&issue_list_widget_state.selected_view_id][index]
-                                            .clone()
-                                    });
-                                self.selected_issue_widget
-                                    .set_selected_issue(selected_issue);
+                }
+                (_, InputMode::Normal, Some(Action::Search)) => {
+                    self.board_widget.close();
+                    self.issue_list_widget.toggle_search_mode();
+                }
+                (_, InputMode::Normal, Some(Action::Retry)) => {
+                    self.tab_widget.retry();
+                }
+                (_, InputMode::Normal, Some(Action::Filter)) => {
+                    self.board_widget.close();
+                    self.issue_list_widget.toggle_filter_mode();
+                }
+                (_, InputMode::Normal, Some(Action::Board)) => {
+                    if self.board_widget.visible {
+                        self.board_widget.close();
+                    } else {
+                        self.board_widget.open();
+                    }
+                }
+                (_, InputMode::Normal, _) if self.board_widget.visible => {
+                    match self.board_widget.handle_event(event) {
+                        LtEvent::SetIssueState(issue_id, workflow_state) => {
+                            self.issue_list_widget.update_issue_state(
+                                &issue_id,
+                                workflow_state,
+                                self.selected_issue_widget.clone(),
+                            );
+                        }
+                        LtEvent::SelectIssue => {
+                            let issue = self.board_widget.selected_issue();
+                            if let Some(issue) = &issue {
+                                self.issue_list_widget.select_issue(&issue.id);
                             }
-                            LtEvent::SearchIssues(_) => {
-                                self.tab_widget.show_and_select_search_tab();
+                            self.selected_issue_widget.set_selected_issue(issue);
+                            self.board_widget.close();
+                        }
+                        LtEvent::ChangeIssueState => {
+                            if let Some(issue) = self.board_widget.selected_issue() {
+                                self.state_picker_widget.open(issue);
                             }
-                            _ => (),
                         }
+                        _ => (),
                     }
-                };
-            }
+                }
+                (_, InputMode::Normal, Some(Action::Relations)) => {
+                    self.open_relation_picker();
+                }
+                (_, InputMode::Normal, Some(Action::OpenUrl)) => {
+                    if let Some(issue) = self.selected_issue_widget.selected_issue() {
+                        let _ = open::that(&issue.url);
+                    }
+                }
+                (_, InputMode::Normal, Some(Action::CopyBranch)) => {
+                    if let Some(issue) = self.selected_issue_widget.selected_issue() {
+                        let _ = cli_clipboard::set_contents(issue.branch_name);
+                    }
+                }
+                (_, InputMode::Normal, Some(Action::Checkout)) => {
+                    self.start_branch(false);
+                }
+                (_, InputMode::Normal, Some(Action::Worktree)) => {
+                    self.start_branch(true);
+                }
+                (_, input_mode, _) => {
+                    if input_mode == InputMode::Normal
+                        && let LtEvent::EditDescription =
+                            self.selected_issue_widget.handle_event(event)
+                    {
+                        self.editing_description = true;
+                    }
+                    match self.issue_list_widget.handle_event(event) {
+                        LtEvent::SelectIssue => {
+                            let selected_issue = self.issue_list_widget.selected_issue();
+                            self.selected_issue_widget
+                                .set_selected_issue(selected_issue);
+                        }
+                        LtEvent::SearchIssues(_) => {
+                            self.projects_widget.close();
+                            self.cycles_widget.close();
+                            self.tab_widget.show_and_select_search_tab();
+                        }
+                        LtEvent::ChangeIssueState => {
+                            if let Some(issue) = self.selected_issue_widget.selected_issue() {
+                                self.state_picker_widget.open(issue);
+                            }
+                        }
+                        LtEvent::NewIssue => {
+                            self.issue_form_widget
+                                .open(self.selected_issue_widget.selected_issue());
+                        }
+                        _ => (),
+                    }
+                }
+            };
         }
     }
 }
@@ -185,16 +630,20 @@ impl fmt::Display for LoadingState {
 
 #[derive(Deserialize, Serialize, Debug, Default, Clone)]
 pub struct IssueFragment {
+    pub id: String,
     pub title: String,
     pub identifier: String,
     pub state: IssueFragmentState,
     pub url: String,
+    pub team: IssueFragmentTeam,
     pub assignee: Option<IssueFragmentAssignee>,
     pub creator: Option<IssueFragmentCreator>,
     pub estimate: Option<f64>,
     pub project: Option<IssueFragmentProject>,
     #[serde(rename = "createdAt")]
     pub created_at: String,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: String,
     #[serde(rename = "priorityLabel")]
     pub priority_label: String,
     pub priority: f64,
@@ -202,23 +651,54 @@ pub struct IssueFragment {
     #[serde(rename = "branchName")]
     pub branch_name: String,
     pub description: Option<String>,
+    // issues with a "blocks" relation to this one
+    #[serde(default)]
+    pub blockers: Vec<IssueBlocker>,
+}
+
+impl IssueFragment {
+    // another issue that isn't done or cancelled blocks this one
+    pub fn blocked(&self) -> bool {
+        self.blockers
+            .iter()
+            .any(|blocker| !matches!(blocker.state_type.as_str(), "completed" | "cancelled"))
+    }
+}
+
+// a blocking issue and the state type it was last seen in, which is kept up to date as fresher
+// copies of the blocker come in
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+pub struct IssueBlocker {
+    pub id: String,
+    pub state_type: String,
 }
 
 #[duplicate_item(
     from_type   to_type;
     [ custom_view_query::IssueFragment] [ IssueFragment ];
+    [ custom_view_updated_query::IssueFragment] [ IssueFragment ];
+    [ my_issues_updated_query::IssueFragment] [ IssueFragment ];
     [ my_issues_query::IssueFragment] [ IssueFragment ];
     [ search_query::IssueFragment] [ IssueFragment ];
+    [ issue_query::IssueFragment] [ IssueFragment ];
+    [ issue_update_mutation::IssueFragment ] [ IssueFragment ];
+    [ issue_create_mutation::IssueFragment ] [ IssueFragment ];
+    [ notifications_query::IssueFragment ] [ IssueFragment ];
+    [ issue_vcs_branch_search_query::IssueFragment ] [ IssueFragment ];
+    [ filtered_issues_query::IssueFragment ] [ IssueFragment ];
 )]
 impl From<from_type> for to_type {
     fn from(item: from_type) -> Self {
         Self {
+            id: item.id,
             title: item.title,
             identifier: item.identifier,
             url: item.url,
+            team: item.team.into(),
             estimate: item.estimate,
             state: item.state.into(),
             created_at: item.created_at,
+            updated_at: item.updated_at,
             priority: item.priority,
             priority_label: item.priority_label,
             branch_name: item.branch_name,
@@ -227,12 +707,23 @@ impl From<from_type> for to_type {
             assignee: item.assignee.map(|assignee| assignee.into()),
             creator: item.creator.map(|creator| creator.into()),
             project: item.project.map(|project| project.into()),
+            blockers: item
+                .inverse_relations
+                .nodes
+                .into_iter()
+                .filter(|relation| relation.type_ == "blocks")
+                .map(|relation| IssueBlocker {
+                    id: relation.issue.id,
+                    state_type: relation.issue.state.type_,
+                })
+                .collect(),
         }
     }
 }
 
 #[derive(Deserialize, Serialize, Debug, Default, Clone)]
 pub struct IssueFragmentState {
+    pub id: String,
     pub name: String,
     pub color: String,
     #[serde(rename = "type")]
@@ -242,12 +733,21 @@ pub struct IssueFragmentState {
 #[duplicate_item(
     from_type   to_type;
     [ custom_view_query::IssueFragmentState ] [ IssueFragmentState ];
+    [ custom_view_updated_query::IssueFragmentState ] [ IssueFragmentState ];
+    [ my_issues_updated_query::IssueFragmentState ] [ IssueFragmentState ];
     [ my_issues_query::IssueFragmentState ] [ IssueFragmentState ];
     [ search_query::IssueFragmentState ] [ IssueFragmentState ];
+    [ issue_query::IssueFragmentState ] [ IssueFragmentState ];
+    [ issue_update_mutation::IssueFragmentState ] [ IssueFragmentState ];
+    [ issue_create_mutation::IssueFragmentState ] [ IssueFragmentState ];
+    [ notifications_query::IssueFragmentState ] [ IssueFragmentState ];
+    [ issue_vcs_branch_search_query::IssueFragmentState ] [ IssueFragmentState ];
+    [ filtered_issues_query::IssueFragmentState ] [ IssueFragmentState ];
 )]
 impl From<from_type> for to_type {
     fn from(item: from_type) -> Self {
         Self {
+            id: item.id,
             name: item.name,
             color: item.color,
             type_: item.type_,
@@ -255,6 +755,35 @@ impl From<from_type> for to_type {
     }
 }
 
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub struct IssueFragmentTeam {
+    pub id: String,
+    pub key: String,
+}
+
+#[duplicate_item(
+    from_type   to_type;
+    [ custom_view_query::IssueFragmentTeam ] [ IssueFragmentTeam ];
+    [ custom_view_updated_query::IssueFragmentTeam ] [ IssueFragmentTeam ];
+    [ my_issues_updated_query::IssueFragmentTeam ] [ IssueFragmentTeam ];
+    [ my_issues_query::IssueFragmentTeam ] [ IssueFragmentTeam ];
+    [ search_query::IssueFragmentTeam ] [ IssueFragmentTeam ];
+    [ issue_query::IssueFragmentTeam ] [ IssueFragmentTeam ];
+    [ issue_update_mutation::IssueFragmentTeam ] [ IssueFragmentTeam ];
+    [ issue_create_mutation::IssueFragmentTeam ] [ IssueFragmentTeam ];
+    [ notifications_query::IssueFragmentTeam ] [ IssueFragmentTeam ];
+    [ issue_vcs_branch_search_query::IssueFragmentTeam ] [ IssueFragmentTeam ];
+    [ filtered_issues_query::IssueFragmentTeam ] [ IssueFragmentTeam ];
+)]
+impl From<from_type> for to_type {
+    fn from(item: from_type) -> Self {
+        Self {
+            id: item.id,
+            key: item.key,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Default, Clone)]
 pub struct IssueFragmentAssignee {
     #[serde(rename = "isMe")]
@@ -266,8 +795,16 @@ pub struct IssueFragmentAssignee {
 #[duplicate_item(
     from_type   to_type;
     [ custom_view_query::IssueFragmentAssignee ] [ IssueFragmentAssignee ];
+    [ custom_view_updated_query::IssueFragmentAssignee ] [ IssueFragmentAssignee ];
+    [ my_issues_updated_query::IssueFragmentAssignee ] [ IssueFragmentAssignee ];
     [ my_issues_query::IssueFragmentAssignee ] [ IssueFragmentAssignee ];
     [ search_query::IssueFragmentAssignee ] [ IssueFragmentAssignee ];
+    [ issue_query::IssueFragmentAssignee ] [ IssueFragmentAssignee ];
+    [ issue_update_mutation::IssueFragmentAssignee ] [ IssueFragmentAssignee ];
+    [ issue_create_mutation::IssueFragmentAssignee ] [ IssueFragmentAssignee ];
+    [ notifications_query::IssueFragmentAssignee ] [ IssueFragmentAssignee ];
+    [ issue_vcs_branch_search_query::IssueFragmentAssignee ] [ IssueFragmentAssignee ];
+    [ filtered_issues_query::IssueFragmentAssignee ] [ IssueFragmentAssignee ];
 )]
 impl From<from_type> for to_type {
     fn from(item: from_type) -> Self {
@@ -289,8 +826,16 @@ pub struct IssueFragmentCreator {
 #[duplicate_item(
     from_type   to_type;
     [ custom_view_query::IssueFragmentCreator ] [ IssueFragmentCreator ];
+    [ custom_view_updated_query::IssueFragmentCreator ] [ IssueFragmentCreator ];
+    [ my_issues_updated_query::IssueFragmentCreator ] [ IssueFragmentCreator ];
     [ my_issues_query::IssueFragmentCreator ] [ IssueFragmentCreator ];
     [ search_query::IssueFragmentCreator ] [ IssueFragmentCreator ];
+    [ issue_query::IssueFragmentCreator ] [ IssueFragmentCreator ];
+    [ issue_update_mutation::IssueFragmentCreator ] [ IssueFragmentCreator ];
+    [ issue_create_mutation::IssueFragmentCreator ] [ IssueFragmentCreator ];
+    [ notifications_query::IssueFragmentCreator ] [ IssueFragmentCreator ];
+    [ issue_vcs_branch_search_query::IssueFragmentCreator ] [ IssueFragmentCreator ];
+    [ filtered_issues_query::IssueFragmentCreator ] [ IssueFragmentCreator ];
 )]
 impl From<from_type> for to_type {
     fn from(item: from_type) -> Self {
@@ -311,8 +856,16 @@ pub struct IssueFragmentProject {
 #[duplicate_item(
     from_type   to_type;
     [ custom_view_query::IssueFragmentProject ] [ IssueFragmentProject ];
+    [ custom_view_updated_query::IssueFragmentProject ] [ IssueFragmentProject ];
+    [ my_issues_updated_query::IssueFragmentProject ] [ IssueFragmentProject ];
     [ my_issues_query::IssueFragmentProject ] [ IssueFragmentProject ];
     [ search_query::IssueFragmentProject ] [ IssueFragmentProject ];
+    [ issue_query::IssueFragmentProject ] [ IssueFragmentProject ];
+    [ issue_update_mutation::IssueFragmentProject ] [ IssueFragmentProject ];
+    [ issue_create_mutation::IssueFragmentProject ] [ IssueFragmentProject ];
+    [ notifications_query::IssueFragmentProject ] [ IssueFragmentProject ];
+    [ issue_vcs_branch_search_query::IssueFragmentProject ] [ IssueFragmentProject ];
+    [ filtered_issues_query::IssueFragmentProject ] [ IssueFragmentProject ];
 )]
 impl From<from_type> for to_type {
     fn from(item: from_type) -> Self {
@@ -332,8 +885,16 @@ pub struct IssueFragmentLabels {
 #[duplicate_item(
     from_type   to_type;
     [ custom_view_query::IssueFragmentLabels ] [ IssueFragmentLabels ];
+    [ custom_view_updated_query::IssueFragmentLabels ] [ IssueFragmentLabels ];
+    [ my_issues_updated_query::IssueFragmentLabels ] [ IssueFragmentLabels ];
     [ my_issues_query::IssueFragmentLabels ] [ IssueFragmentLabels ];
     [ search_query::IssueFragmentLabels ] [ IssueFragmentLabels ];
+    [ issue_query::IssueFragmentLabels ] [ IssueFragmentLabels ];
+    [ issue_update_mutation::IssueFragmentLabels ] [ IssueFragmentLabels ];
+    [ issue_create_mutation::IssueFragmentLabels ] [ IssueFragmentLabels ];
+    [ notifications_query::IssueFragmentLabels ] [ IssueFragmentLabels ];
+    [ issue_vcs_branch_search_query::IssueFragmentLabels ] [ IssueFragmentLabels ];
+    [ filtered_issues_query::IssueFragmentLabels ] [ IssueFragmentLabels ];
 )]
 impl From<from_type> for to_type {
     fn from(item: from_type) -> Self {
@@ -355,8 +916,16 @@ pub struct IssueFragmentLabelsEdges {
 #[duplicate_item(
     from_type   to_type;
     [ custom_view_query::IssueFragmentLabelsEdges ] [ IssueFragmentLabelsEdges ];
+    [ custom_view_updated_query::IssueFragmentLabelsEdges ] [ IssueFragmentLabelsEdges ];
+    [ my_issues_updated_query::IssueFragmentLabelsEdges ] [ IssueFragmentLabelsEdges ];
     [ my_issues_query::IssueFragmentLabelsEdges ] [ IssueFragmentLabelsEdges ];
     [ search_query::IssueFragmentLabelsEdges ] [ IssueFragmentLabelsEdges ];
+    [ issue_query::IssueFragmentLabelsEdges ] [ IssueFragmentLabelsEdges ];
+    [ issue_update_mutation::IssueFragmentLabelsEdges ] [ IssueFragmentLabelsEdges ];
+    [ issue_create_mutation::IssueFragmentLabelsEdges ] [ IssueFragmentLabelsEdges ];
+    [ notifications_query::IssueFragmentLabelsEdges ] [ IssueFragmentLabelsEdges ];
+    [ issue_vcs_branch_search_query::IssueFragmentLabelsEdges ] [ IssueFragmentLabelsEdges ];
+    [ filtered_issues_query::IssueFragmentLabelsEdges ] [ IssueFragmentLabelsEdges ];
 )]
 impl From<from_type> for to_type {
     fn from(item: from_type) -> Self {
@@ -375,8 +944,16 @@ pub struct IssueFragmentLabelsEdgesNode {
 #[duplicate_item(
     from_type   to_type;
     [ custom_view_query::IssueFragmentLabelsEdgesNode ] [ IssueFragmentLabelsEdgesNode ];
+    [ custom_view_updated_query::IssueFragmentLabelsEdgesNode ] [ IssueFragmentLabelsEdgesNode ];
+    [ my_issues_updated_query::IssueFragmentLabelsEdgesNode ] [ IssueFragmentLabelsEdgesNode ];
     [ my_issues_query::IssueFragmentLabelsEdgesNode ] [ IssueFragmentLabelsEdgesNode ];
     [ search_query::IssueFragmentLabelsEdgesNode ] [ IssueFragmentLabelsEdgesNode ];
+    [ issue_query::IssueFragmentLabelsEdgesNode ] [ IssueFragmentLabelsEdgesNode ];
+    [ issue_update_mutation::IssueFragmentLabelsEdgesNode ] [ IssueFragmentLabelsEdgesNode ];
+    [ issue_create_mutation::IssueFragmentLabelsEdgesNode ] [ IssueFragmentLabelsEdgesNode ];
+    [ notifications_query::IssueFragmentLabelsEdgesNode ] [ IssueFragmentLabelsEdgesNode ];
+    [ issue_vcs_branch_search_query::IssueFragmentLabelsEdgesNode ] [ IssueFragmentLabelsEdgesNode ];
+    [ filtered_issues_query::IssueFragmentLabelsEdgesNode ] [ IssueFragmentLabelsEdgesNode ];
 )]
 impl From<from_type> for to_type {
     fn from(item: from_type) -> Self {
@@ -386,3 +963,77 @@ impl From<from_type> for to_type {
         }
     }
 }
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub struct CommentFragment {
+    pub id: String,
+    pub body: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    pub user: Option<CommentFragmentUser>,
+}
+
+#[duplicate_item(
+    from_type   to_type;
+    [ issue_comments_query::CommentFragment ] [ CommentFragment ];
+    [ comment_create_mutation::CommentFragment ] [ CommentFragment ];
+)]
+impl From<from_type> for to_type {
+    fn from(item: from_type) -> Self {
+        Self {
+            id: item.id,
+            body: item.body,
+            created_at: item.created_at,
+            user: item.user.map(|user| user.into()),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub struct CommentFragmentUser {
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    #[serde(rename = "isMe")]
+    pub is_me: bool,
+}
+
+#[duplicate_item(
+    from_type   to_type;
+    [ issue_comments_query::CommentFragmentUser ] [ CommentFragmentUser ];
+    [ comment_create_mutation::CommentFragmentUser ] [ CommentFragmentUser ];
+)]
+impl From<from_type> for to_type {
+    fn from(item: from_type) -> Self {
+        Self {
+            display_name: item.display_name,
+            is_me: item.is_me,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use crate::{App, config::Config, stub_server::StubServer};
+
+    #[tokio::test]
+    async fn test_start() {
+        let server = StubServer::start();
+        // background refreshes are off by default
+        let mut app = App::new(server.client(), Config::default());
+        assert!(app.start().is_none());
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+
+        let config = Config {
+            refresh_interval: Some(Duration::from_secs(120)),
+            ..Config::default()
+        };
+        let mut app = App::new(server.client(), config);
+        let refresh = app.start().unwrap();
+        assert_eq!(refresh.period(), Duration::from_secs(120));
+    }
+}
@@ -0,0 +1,288 @@
+// Client-side sorting and grouping of an issue list, chosen per tab.
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{IssueFragment, widgets::STATE_TYPE_ORDER};
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    // the order the view itself returns issues in
+    #[default]
+    View,
+    Priority,
+    Created,
+    Updated,
+    State,
+    Estimate,
+    Identifier,
+}
+
+impl SortKey {
+    const ALL: [SortKey; 7] = [
+        SortKey::View,
+        SortKey::Priority,
+        SortKey::Created,
+        SortKey::Updated,
+        SortKey::State,
+        SortKey::Estimate,
+        SortKey::Identifier,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortKey::View => "view order",
+            SortKey::Priority => "priority",
+            SortKey::Created => "created",
+            SortKey::Updated => "updated",
+            SortKey::State => "status",
+            SortKey::Estimate => "estimate",
+            SortKey::Identifier => "identifier",
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupKey {
+    #[default]
+    None,
+    Project,
+    State,
+    Assignee,
+    Label,
+}
+
+impl GroupKey {
+    const ALL: [GroupKey; 5] = [
+        GroupKey::None,
+        GroupKey::Project,
+        GroupKey::State,
+        GroupKey::Assignee,
+        GroupKey::Label,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GroupKey::None => "nothing",
+            GroupKey::Project => "project",
+            GroupKey::State => "status",
+            GroupKey::Assignee => "assignee",
+            GroupKey::Label => "label",
+        }
+    }
+}
+
+fn next<T: Copy + PartialEq>(all: &[T], current: T) -> T {
+    let index = all.iter().position(|item| *item == current).unwrap_or(0);
+    all[(index + 1) % all.len()]
+}
+
+// "no priority" (0) sorts last
+pub fn priority_rank(issue: &IssueFragment) -> f64 {
+    if issue.priority == 0.0 {
+        5.0
+    } else {
+        issue.priority
+    }
+}
+
+fn state_rank(issue: &IssueFragment) -> usize {
+    STATE_TYPE_ORDER
+        .iter()
+        .position(|type_| *type_ == issue.state.type_)
+        .unwrap_or(STATE_TYPE_ORDER.len())
+}
+
+// ENG-9 sorts before ENG-10
+fn identifier_key(issue: &IssueFragment) -> (&str, u64) {
+    match issue.identifier.rsplit_once('-') {
+        Some((team, number)) => (team, number.parse().unwrap_or(0)),
+        None => (&issue.identifier, 0),
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ViewOrdering {
+    #[serde(default)]
+    pub sort: SortKey,
+    #[serde(default)]
+    pub group: GroupKey,
+}
+
+impl ViewOrdering {
+    pub fn next_sort(&mut self) {
+        self.sort = next(&SortKey::ALL, self.sort);
+    }
+
+    pub fn next_group(&mut self) {
+        self.group = next(&GroupKey::ALL, self.group);
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn group_name<'a>(&self, issue: &'a IssueFragment) -> Option<&'a str> {
+        match self.group {
+            GroupKey::None => None,
+            GroupKey::Project => issue.project.as_ref().map(|project| project.name.as_str()),
+            GroupKey::State => Some(&issue.state.name),
+            GroupKey::Assignee => issue
+                .assignee
+                .as_ref()
+                .map(|assignee| assignee.display_name.as_str()),
+            // an issue with several labels is only listed under its first one
+            GroupKey::Label => issue
+                .labels
+                .edges
+                .first()
+                .map(|edge| edge.node.name.as_str()),
+        }
+    }
+
+    // the header an issue is listed under, None when the list isn't grouped
+    pub fn group_label(&self, issue: &IssueFragment) -> Option<String> {
+        let missing = match self.group {
+            GroupKey::None => return None,
+            GroupKey::Project => "No project",
+            GroupKey::State => "No status",
+            GroupKey::Assignee => "Unassigned",
+            GroupKey::Label => "No label",
+        };
+        Some(self.group_name(issue).unwrap_or(missing).to_string())
+    }
+
+    // groups by status follow the workflow, other groups are alphabetical with the ungrouped last
+    fn compare_groups(&self, a: &IssueFragment, b: &IssueFragment) -> Ordering {
+        match self.group {
+            GroupKey::None => Ordering::Equal,
+            GroupKey::State => state_rank(a)
+                .cmp(&state_rank(b))
+                .then_with(|| a.state.name.cmp(&b.state.name)),
+            _ => {
+                let key = |issue| {
+                    let name = self.group_name(issue);
+                    (name.is_none(), name.map(str::to_lowercase))
+                };
+                key(a).cmp(&key(b))
+            }
+        }
+    }
+
+    fn compare(&self, a: &IssueFragment, b: &IssueFragment) -> Ordering {
+        let by_key = match self.sort {
+            // the sort is stable, so equal issues keep the view's order
+            SortKey::View => return self.compare_groups(a, b),
+            SortKey::Priority => priority_rank(a).total_cmp(&priority_rank(b)),
+            // newest first; RFC 3339 timestamps sort as strings
+            SortKey::Created => b.created_at.cmp(&a.created_at),
+            SortKey::Updated => b.updated_at.cmp(&a.updated_at),
+            SortKey::State => state_rank(a).cmp(&state_rank(b)),
+            // biggest first, unestimated last
+            SortKey::Estimate => match (a.estimate, b.estimate) {
+                (Some(a), Some(b)) => b.total_cmp(&a),
+                (a, b) => b.is_some().cmp(&a.is_some()),
+            },
+            SortKey::Identifier => identifier_key(a).cmp(&identifier_key(b)),
+        };
+        self.compare_groups(a, b)
+            .then(by_key)
+            .then_with(|| priority_rank(a).total_cmp(&priority_rank(b)))
+    }
+
+    // the positions of `issues` in the order they're listed in
+    pub fn sort(&self, issues: &[IssueFragment]) -> Vec<usize> {
+        let mut positions: Vec<usize> = (0..issues.len()).collect();
+        positions.sort_by(|a, b| self.compare(&issues[*a], &issues[*b]));
+        positions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::widgets::make_issue;
+
+    use super::*;
+
+    fn identifiers(ordering: ViewOrdering, issues: &[IssueFragment]) -> Vec<String> {
+        ordering
+            .sort(issues)
+            .into_iter()
+            .map(|position| issues[position].identifier.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_and_group() {
+        let mut first = make_issue("First", "ENG-9");
+        first.priority = 0.0;
+        first.created_at = String::from("2025-01-01T00:00:00Z");
+        first.estimate = Some(2.0);
+        let mut second = make_issue("Second", "ENG-10");
+        second.priority = 2.0;
+        second.created_at = String::from("2025-03-01T00:00:00Z");
+        second.project = None;
+        let mut third = make_issue("Third", "ENG-11");
+        third.priority = 3.0;
+        third.created_at = String::from("2025-02-01T00:00:00Z");
+        third.estimate = Some(5.0);
+        let issues = [first, second, third];
+
+        // the view's own order is kept until a sort is picked
+        let mut ordering = ViewOrdering::default();
+        assert_eq!(
+            identifiers(ordering, &issues),
+            ["ENG-9", "ENG-10", "ENG-11"]
+        );
+        ordering.next_sort();
+        assert_eq!(ordering.sort, SortKey::Priority);
+        assert_eq!(
+            identifiers(ordering, &issues),
+            ["ENG-10", "ENG-11", "ENG-9"]
+        );
+        ordering.next_sort();
+        assert_eq!(ordering.sort, SortKey::Created);
+        assert_eq!(
+            identifiers(ordering, &issues),
+            ["ENG-10", "ENG-11", "ENG-9"]
+        );
+        ordering.sort = SortKey::Estimate;
+        assert_eq!(
+            identifiers(ordering, &issues),
+            ["ENG-11", "ENG-9", "ENG-10"]
+        );
+        ordering.sort = SortKey::Identifier;
+        assert_eq!(
+            identifiers(ordering, &issues),
+            ["ENG-9", "ENG-10", "ENG-11"]
+        );
+
+        // issues without a project are grouped last
+        ordering.next_group();
+        assert_eq!(ordering.group, GroupKey::Project);
+        assert_eq!(
+            identifiers(ordering, &issues),
+            ["ENG-9", "ENG-11", "ENG-10"]
+        );
+        assert_eq!(
+            ordering.group_label(&issues[1]).as_deref(),
+            Some("No project")
+        );
+
+        // grouping keeps the view's order within each group
+        ordering.sort = SortKey::View;
+        assert_eq!(
+            identifiers(ordering, &issues),
+            ["ENG-9", "ENG-11", "ENG-10"]
+        );
+
+        ordering.sort = SortKey::Identifier;
+        ordering.group = GroupKey::Label;
+        ordering.next_group();
+        assert_eq!(ordering.group, GroupKey::None);
+        ordering.next_sort();
+        assert_eq!(ordering.sort, SortKey::View);
+    }
+}
@@ -1,6 +1,11 @@
 use graphql_client::GraphQLQuery;
 
 type DateTime = String;
+type DateTimeOrDuration = String;
+type TimelessDate = String;
+type TimelessDateOrDuration = String;
+#[allow(clippy::upper_case_acronyms)]
+type JSON = serde_json::Value;
 
 #[derive(Debug, Default, GraphQLQuery)]
 #[graphql(
@@ -10,6 +15,14 @@ type DateTime = String;
 )]
 pub struct MyIssuesQuery;
 
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/basic.graphql",
+    response_derives = "serde::Serialize,Default,Debug,Clone"
+)]
+pub struct MyIssuesUpdatedQuery;
+
 #[derive(Debug, Default, GraphQLQuery)]
 #[graphql(
     schema_path = "src/schemas/linear.graphql",
@@ -26,6 +39,21 @@ pub struct CustomViewsQuery;
 )]
 pub struct CustomViewQuery;
 
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/custom_view.graphql",
+    response_derives = "serde::Serialize,Default,Debug,Clone"
+)]
+pub struct CustomViewUpdatedQuery;
+
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/issue_changes.graphql",
+    response_derives = "serde::Serialize,Default,Debug,Clone"
+)]
+pub struct IssueChangesQuery;
 
 #[derive(Debug, Default, GraphQLQuery)]
 #[graphql(
@@ -35,7 +63,158 @@ pub struct CustomViewQuery;
 )]
 pub struct SearchQuery;
 
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/issue.graphql",
+    response_derives = "serde::Serialize,Default,Debug,Clone"
+)]
+pub struct IssueQuery;
+
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/issue.graphql",
+    response_derives = "serde::Serialize,Default,Debug,Clone"
+)]
+pub struct IssueVcsBranchSearchQuery;
+
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/workflow_states.graphql",
+    response_derives = "serde::Serialize,Default,Debug,Clone,PartialEq"
+)]
+pub struct WorkflowStatesQuery;
+
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/issue_update.graphql",
+    response_derives = "Default,Debug,Clone",
+    variables_derives = "Default,Debug",
+    skip_serializing_none
+)]
+pub struct IssueUpdateMutation;
+
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/issue_form.graphql",
+    response_derives = "serde::Serialize,Default,Debug,Clone"
+)]
+pub struct IssueFormQuery;
+
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/issue_create.graphql",
+    response_derives = "Default,Debug,Clone",
+    variables_derives = "Default,Debug,PartialEq,Clone",
+    skip_serializing_none
+)]
+pub struct IssueCreateMutation;
+
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/comments.graphql",
+    response_derives = "serde::Serialize,Default,Debug,Clone"
+)]
+pub struct IssueCommentsQuery;
+
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/issue_hierarchy.graphql",
+    response_derives = "serde::Serialize,Default,Debug,Clone"
+)]
+pub struct IssueHierarchyQuery;
+
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/issue_relations.graphql",
+    response_derives = "serde::Serialize,Default,Debug,Clone,PartialEq"
+)]
+pub struct IssueRelationsQuery;
 
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/issue_relations.graphql",
+    response_derives = "Default,Debug,Clone",
+    variables_derives = "Debug,PartialEq"
+)]
+pub struct IssueRelationCreateMutation;
 
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/issue_relations.graphql",
+    response_derives = "Default,Debug,Clone"
+)]
+pub struct IssueRelationDeleteMutation;
 
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/comment_create.graphql",
+    response_derives = "serde::Serialize,Default,Debug,Clone",
+    variables_derives = "Default,Debug",
+    skip_serializing_none
+)]
+pub struct CommentCreateMutation;
 
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/notifications.graphql",
+    response_derives = "serde::Serialize,Debug,Clone"
+)]
+pub struct NotificationsQuery;
+
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/notifications.graphql",
+    response_derives = "Default,Debug,Clone",
+    variables_derives = "Default,Debug",
+    skip_serializing_none
+)]
+pub struct NotificationUpdateMutation;
+
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/notifications.graphql",
+    response_derives = "Default,Debug,Clone"
+)]
+pub struct NotificationArchiveMutation;
+
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/projects.graphql",
+    response_derives = "Debug,Clone"
+)]
+pub struct ProjectsQuery;
+
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/cycles.graphql",
+    response_derives = "serde::Serialize,Default,Debug,Clone"
+)]
+pub struct CyclesQuery;
+
+#[derive(Debug, Default, GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schemas/linear.graphql",
+    query_path = "src/queries/filtered_issues.graphql",
+    variables_derives = "Default",
+    // the filter's enums come with their own Serialize impl
+    response_derives = "Default,Debug,Clone",
+    skip_serializing_none
+)]
+pub struct FilteredIssuesQuery;
@@ -0,0 +1,148 @@
+// A tiny HTTP server standing in for the Linear API in tests. Each GraphQL request is
+// answered with the recorded response in tests/fixtures/<operationName>.json.
+use std::{
+    collections::{HashMap, VecDeque},
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use crate::api::LinearClient;
+
+#[derive(Debug, Clone)]
+pub struct StubServer {
+    pub endpoint: String,
+    requests: Arc<Mutex<Vec<serde_json::Value>>>,
+    // responses that replace the recorded fixture for an operation
+    overrides: Arc<Mutex<HashMap<String, String>>>,
+    // status codes to answer the next requests with before going back to the fixtures
+    failures: Arc<Mutex<VecDeque<u16>>>,
+}
+
+impl StubServer {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let server = Self {
+            endpoint: format!("http://{}/graphql", listener.local_addr().unwrap()),
+            requests: Arc::default(),
+            overrides: Arc::default(),
+            failures: Arc::default(),
+        };
+        let this = server.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let this = this.clone();
+                thread::spawn(move || this.serve(stream));
+            }
+        });
+        server
+    }
+
+    pub fn respond_with(&self, operation_name: &str, body: &str) {
+        self.overrides
+            .lock()
+            .unwrap()
+            .insert(operation_name.to_string(), body.to_string());
+    }
+
+    // answers the next `times` requests with `status` and an immediate Retry-After
+    pub fn fail_next(&self, times: usize, status: u16) {
+        self.failures
+            .lock()
+            .unwrap()
+            .extend(std::iter::repeat_n(status, times));
+    }
+
+    pub fn client(&self) -> Arc<LinearClient> {
+        Arc::new(LinearClient::new(String::from("test-token"), self.endpoint.clone()).unwrap())
+    }
+
+    // operation names of every request received so far, in order
+    pub fn operations(&self) -> Vec<String> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|request| Some(request.get("operationName")?.as_str()?.to_string()))
+            .collect()
+    }
+
+    // handles requests on one keep-alive connection until the client hangs up
+    fn serve(&self, stream: TcpStream) {
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut stream = stream;
+        loop {
+            let mut content_length = 0;
+            let mut line = String::new();
+            loop {
+                line.clear();
+                if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                    return;
+                }
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    content_length = value.trim().parse().unwrap_or(0);
+                }
+            }
+            let mut body = vec![0; content_length];
+            if reader.read_exact(&mut body).is_err() {
+                return;
+            }
+
+            let request: serde_json::Value = serde_json::from_slice(&body).unwrap_or_default();
+            let operation_name = request
+                .get("operationName")
+                .and_then(|name| name.as_str())
+                .unwrap_or_default()
+                .to_string();
+            self.requests.lock().unwrap().push(request);
+
+            if let Some(status) = self.failures.lock().unwrap().pop_front() {
+                let written = write!(
+                    stream,
+                    "HTTP/1.1 {} Failed\r\nRetry-After: 0\r\nContent-Length: 0\r\n\r\n",
+                    status
+                );
+                if written.is_err() {
+                    return;
+                }
+                continue;
+            }
+
+            let response = self
+                .overrides
+                .lock()
+                .unwrap()
+                .get(&operation_name)
+                .cloned()
+                .unwrap_or_else(|| fixture(&operation_name));
+            let written = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                response.len(),
+                response
+            );
+            if written.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+fn fixture(operation_name: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(format!("{}.json", operation_name));
+    std::fs::read_to_string(path).unwrap_or_else(|_| {
+        format!(
+            r#"{{"errors": [{{"message": "no fixture for {}"}}]}}"#,
+            operation_name
+        )
+    })
+}
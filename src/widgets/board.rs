@@ -0,0 +1,538 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+use crossterm::event::{Event, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize, palette::material::AMBER},
+    text::{Line, Span, Text},
+    widgets::{Block, List, ListItem, ListState, Paragraph, StatefulWidget, Widget},
+};
+
+use crate::{
+    IssueFragment, LtEvent,
+    api::LinearClient,
+    config::{Action, Keymap},
+    iconmap,
+    queries::{
+        WorkflowStatesQuery,
+        workflow_states_query::{self, WorkflowStateFragment},
+    },
+    widgets::{MyIssuesWidget, state_picker::STATE_TYPE_ORDER},
+};
+
+// columns shown even when empty, so cards can be moved into them
+const FIXED_STATE_TYPES: [&str; 4] = ["backlog", "unstarted", "started", "completed"];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GroupBy {
+    #[default]
+    StateType,
+    StateName,
+}
+
+#[derive(Debug)]
+struct Column {
+    // the state type or state name the column collects
+    key: String,
+    title: String,
+    type_: String,
+    color: String,
+    issues: Vec<IssueFragment>,
+}
+
+fn type_title(type_: &str) -> &str {
+    match type_ {
+        "triage" => "Triage",
+        "backlog" => "Backlog",
+        "unstarted" => "Todo",
+        "started" => "In Progress",
+        "completed" => "Done",
+        "cancelled" => "Canceled",
+        other => other,
+    }
+}
+
+fn type_index(type_: &str) -> usize {
+    STATE_TYPE_ORDER
+        .iter()
+        .position(|t| *t == type_)
+        .unwrap_or(STATE_TYPE_ORDER.len())
+}
+
+// groups the issues into columns in Linear's state order, keeping the view's order within each
+fn columns(issues: &[IssueFragment], group_by: GroupBy) -> Vec<Column> {
+    let mut columns: Vec<Column> = vec![];
+    if group_by == GroupBy::StateType {
+        for type_ in FIXED_STATE_TYPES {
+            columns.push(Column {
+                key: type_.to_string(),
+                title: type_title(type_).to_string(),
+                type_: type_.to_string(),
+                color: String::new(),
+                issues: vec![],
+            });
+        }
+    }
+    for issue in issues {
+        let key = match group_by {
+            GroupBy::StateType => &issue.state.type_,
+            GroupBy::StateName => &issue.state.name,
+        };
+        let index = match columns.iter().position(|column| &column.key == key) {
+            Some(index) => index,
+            None => {
+                columns.push(Column {
+                    key: key.clone(),
+                    title: match group_by {
+                        GroupBy::StateType => type_title(key).to_string(),
+                        GroupBy::StateName => key.clone(),
+                    },
+                    type_: issue.state.type_.clone(),
+                    color: String::new(),
+                    issues: vec![],
+                });
+                columns.len() - 1
+            }
+        };
+        let column = &mut columns[index];
+        if column.color.is_empty() {
+            column.color = issue.state.color.clone();
+        }
+        column.issues.push(issue.clone());
+    }
+    columns.sort_by(|a, b| {
+        type_index(&a.type_)
+            .cmp(&type_index(&b.type_))
+            .then_with(|| match group_by {
+                GroupBy::StateType => std::cmp::Ordering::Equal,
+                GroupBy::StateName => a.title.cmp(&b.title),
+            })
+    });
+    columns
+}
+
+// the state an issue gets when its card is dropped in `column`
+fn target_state(
+    states: &[WorkflowStateFragment],
+    column: &Column,
+    group_by: GroupBy,
+) -> Option<WorkflowStateFragment> {
+    match group_by {
+        GroupBy::StateType => states
+            .iter()
+            .filter(|state| state.type_ == column.key)
+            .min_by(|a, b| a.position.total_cmp(&b.position))
+            .cloned(),
+        GroupBy::StateName => states
+            .iter()
+            .find(|state| state.name == column.key)
+            .cloned(),
+    }
+}
+
+#[derive(Debug, Default)]
+struct BoardWidgetState {
+    group_by: GroupBy,
+    column: usize,
+    // the focused card is followed by id, so focus moves along with it
+    issue_id: Option<String>,
+    // workflow states of the teams on the board, by team id
+    team_states: HashMap<String, Vec<WorkflowStateFragment>>,
+    loading_teams: HashSet<String>,
+    notice: Option<String>,
+}
+
+impl BoardWidgetState {
+    // the focused column and card index
+    fn focus(&self, columns: &[Column]) -> (usize, Option<usize>) {
+        if let Some(issue_id) = &self.issue_id {
+            for (index, column) in columns.iter().enumerate() {
+                if let Some(card) = column.issues.iter().position(|i| &i.id == issue_id) {
+                    return (index, Some(card));
+                }
+            }
+        }
+        let column = self.column.min(columns.len().saturating_sub(1));
+        let card = columns
+            .get(column)
+            .filter(|column| !column.issues.is_empty())
+            .map(|_| 0);
+        (column, card)
+    }
+
+    fn set_focus(&mut self, columns: &[Column], column: usize, card: Option<usize>) {
+        self.column = column;
+        self.issue_id = card
+            .and_then(|card| columns.get(column)?.issues.get(card))
+            .map(|issue| issue.id.clone());
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BoardWidget {
+    state: Arc<RwLock<BoardWidgetState>>,
+    client: Arc<LinearClient>,
+    keymap: Arc<Keymap>,
+    // the board shows whatever view the list has selected
+    issue_list: MyIssuesWidget,
+    pub visible: bool,
+}
+
+impl BoardWidget {
+    pub fn new(client: Arc<LinearClient>, keymap: Arc<Keymap>, issue_list: MyIssuesWidget) -> Self {
+        Self {
+            client,
+            keymap,
+            issue_list,
+            ..Default::default()
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.visible = true;
+        {
+            let mut state = self.state.write().unwrap();
+            state.notice = None;
+            state.issue_id = self.issue_list.selected_issue().map(|issue| issue.id);
+        }
+        self.load_team_states();
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+    }
+
+    // fetches the workflow states of every team on the board that isn't known yet
+    fn load_team_states(&self) {
+        let team_ids: HashSet<String> = self
+            .issue_list
+            .current_issues()
+            .into_iter()
+            .map(|issue| issue.team.id)
+            .collect();
+        let mut state = self.state.write().unwrap();
+        for team_id in team_ids {
+            if state.team_states.contains_key(&team_id) || state.loading_teams.contains(&team_id) {
+                continue;
+            }
+            state.loading_teams.insert(team_id.clone());
+            tokio::spawn(self.clone().fetch_team_states(team_id));
+        }
+    }
+
+    async fn fetch_team_states(self, team_id: String) {
+        let client = &self.client;
+        let variables = workflow_states_query::Variables {
+            team_id: team_id.clone(),
+        };
+        let result = client.query(WorkflowStatesQuery, variables).await;
+        let mut state = self.state.write().unwrap();
+        state.loading_teams.remove(&team_id);
+        match result {
+            Ok(data) => {
+                state.team_states.insert(team_id, data.team.states.nodes);
+            }
+            Err(e) => state.notice = Some(e.to_string()),
+        }
+    }
+
+    pub fn selected_issue(&self) -> Option<IssueFragment> {
+        let state = self.state.read().unwrap();
+        let columns = columns(&self.issue_list.current_issues(), state.group_by);
+        let (column, card) = state.focus(&columns);
+        card.and_then(|card| columns.get(column)?.issues.get(card).cloned())
+    }
+
+    fn move_card(&self, columns: &[Column], offset: isize) -> LtEvent<'static> {
+        let mut state = self.state.write().unwrap();
+        let (column, card) = state.focus(columns);
+        let Some(issue) = card.and_then(|card| columns.get(column)?.issues.get(card)) else {
+            return LtEvent::None;
+        };
+        let Some(target) = column
+            .checked_add_signed(offset)
+            .and_then(|target| columns.get(target))
+        else {
+            return LtEvent::None;
+        };
+        let Some(states) = state.team_states.get(&issue.team.id) else {
+            state.notice = Some(String::from("Loading workflow states…"));
+            drop(state);
+            self.load_team_states();
+            return LtEvent::None;
+        };
+        match target_state(states, target, state.group_by) {
+            Some(workflow_state) => {
+                state.notice = None;
+                state.issue_id = Some(issue.id.clone());
+                LtEvent::SetIssueState(issue.id.clone(), workflow_state)
+            }
+            None => {
+                state.notice = Some(format!("{} has no {} state", issue.team.key, target.title));
+                LtEvent::None
+            }
+        }
+    }
+
+    pub fn handle_event(&self, event: &Event) -> LtEvent<'static> {
+        let Event::Key(key) = event else {
+            return LtEvent::None;
+        };
+        if key.kind != KeyEventKind::Press {
+            return LtEvent::None;
+        }
+        let group_by = self.state.read().unwrap().group_by;
+        let columns = columns(&self.issue_list.current_issues(), group_by);
+        let (column, card) = self.state.read().unwrap().focus(&columns);
+        let card_count = columns.get(column).map_or(0, |column| column.issues.len());
+
+        match self.keymap.action(key) {
+            Some(Action::Down | Action::ScrollDown) if card_count > 0 => {
+                let next = card.map_or(0, |card| (card + 1) % card_count);
+                self.state
+                    .write()
+                    .unwrap()
+                    .set_focus(&columns, column, Some(next));
+            }
+            Some(Action::Up | Action::ScrollUp) if card_count > 0 => {
+                let prev = card.map_or(card_count - 1, |card| (card + card_count - 1) % card_count);
+                self.state
+                    .write()
+                    .unwrap()
+                    .set_focus(&columns, column, Some(prev));
+            }
+            Some(Action::ChangeState) if card.is_some() => {
+                return LtEvent::ChangeIssueState;
+            }
+            Some(Action::ColumnLeft) if column > 0 => {
+                let card = columns[column - 1].issues.first().map(|_| 0);
+                self.state
+                    .write()
+                    .unwrap()
+                    .set_focus(&columns, column - 1, card);
+            }
+            Some(Action::ColumnRight) if column + 1 < columns.len() => {
+                let card = columns[column + 1].issues.first().map(|_| 0);
+                self.state
+                    .write()
+                    .unwrap()
+                    .set_focus(&columns, column + 1, card);
+            }
+            Some(Action::MoveCardLeft) => return self.move_card(&columns, -1),
+            Some(Action::MoveCardRight) => return self.move_card(&columns, 1),
+            Some(Action::Group) => {
+                let mut state = self.state.write().unwrap();
+                state.group_by = match state.group_by {
+                    GroupBy::StateType => GroupBy::StateName,
+                    GroupBy::StateName => GroupBy::StateType,
+                };
+                state.notice = None;
+            }
+            Some(Action::Select) if card.is_some() => return LtEvent::SelectIssue,
+            _ => {}
+        }
+        LtEvent::None
+    }
+}
+
+impl Widget for &BoardWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if !self.visible {
+            return;
+        }
+        let state = self.state.read().unwrap();
+        let columns = columns(&self.issue_list.current_issues(), state.group_by);
+        let (focused_column, focused_card) = state.focus(&columns);
+
+        let grouping = match state.group_by {
+            GroupBy::StateType => "status type",
+            GroupBy::StateName => "status",
+        };
+        let mut block = Block::bordered()
+            .title(Line::from(format!(" Board by {} ", grouping)).bold())
+            .title_bottom(Line::from(vec![
+                Span::from(format!(
+                    " <{}/{}> ",
+                    self.keymap.label(Action::ColumnLeft),
+                    self.keymap.label(Action::ColumnRight)
+                ))
+                .blue(),
+                Span::from("column "),
+                Span::from("─"),
+                Span::from(format!(
+                    " <{}/{}> ",
+                    self.keymap.label(Action::MoveCardLeft),
+                    self.keymap.label(Action::MoveCardRight)
+                ))
+                .blue(),
+                Span::from("move card "),
+                Span::from("─"),
+                Span::from(format!(" <{}> ", self.keymap.label(Action::Group))).blue(),
+                Span::from("group by "),
+                Span::from("─"),
+                Span::from(format!(" <{}> ", self.keymap.label(Action::Select))).blue(),
+                Span::from("open "),
+                Span::from("─"),
+                Span::from(format!(" <{}> ", self.keymap.label(Action::Board))).blue(),
+                Span::from("close "),
+            ]));
+        if let Some(notice) = &state.notice {
+            block = block.title(Line::from(notice.clone().red()).right_aligned());
+        }
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if columns.is_empty() {
+            Paragraph::new("No issues found".red()).render(inner, buf);
+            return;
+        }
+
+        // tests can't see the highlighting
+        let highlight_symbol = if cfg!(test) { ">" } else { "" };
+
+        let areas = Layout::horizontal(vec![Constraint::Fill(1); columns.len()]).split(inner);
+        for (index, (column, column_area)) in columns.iter().zip(areas.iter()).enumerate() {
+            let color = Color::from_str(&column.color).unwrap_or(Color::DarkGray);
+            let mut column_block = Block::bordered().title(Line::from(vec![
+                Span::from(format!(" {}", iconmap::state_to_nf(&column.type_))).fg(color),
+                Span::from(format!("{} ", column.title)).bold(),
+                Span::from(format!("{} ", column.issues.len())).dark_gray(),
+            ]));
+            if index == focused_column {
+                column_block = column_block.border_style(Style::new().fg(Color::Cyan));
+            }
+            let cards: Vec<ListItem> = column
+                .issues
+                .iter()
+                .map(|issue| {
+                    ListItem::new(Text::from(vec![
+                        Line::from(vec![
+                            Span::from(iconmap::p_to_nf(issue.priority)),
+                            Span::from(issue.identifier.clone()).fg(AMBER.c700).bold(),
+                        ]),
+                        Line::from(issue.title.clone()),
+                    ]))
+                })
+                .collect();
+            let mut list_state = ListState::default();
+            if index == focused_column {
+                list_state.select(focused_card);
+            }
+            let list = List::new(cards)
+                .highlight_style(Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+                .highlight_symbol(highlight_symbol)
+                .block(column_block);
+            StatefulWidget::render(list, *column_area, buf, &mut list_state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+    use insta::assert_snapshot;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use crate::{
+        IssueFragment, IssueFragmentState, LtEvent,
+        queries::workflow_states_query::WorkflowStateFragment,
+        widgets::{MyIssuesWidget, create_key_event, make_issue, make_state},
+    };
+
+    use super::{BoardWidget, GroupBy, columns};
+
+    fn issue_in(identifier: &str, state: &WorkflowStateFragment) -> IssueFragment {
+        let mut issue = make_issue(&format!("Ticket {}", identifier), identifier);
+        issue.state = IssueFragmentState {
+            id: state.id.clone(),
+            name: state.name.clone(),
+            color: state.color.clone(),
+            type_: state.type_.clone(),
+        };
+        issue
+    }
+
+    #[test]
+    fn test_columns() {
+        let todo = make_state("todo-id", "Todo", "unstarted", 0.0);
+        let review = make_state("review-id", "In Review", "started", 2.0);
+        let progress = make_state("progress-id", "In Progress", "started", 1.0);
+        let issues = vec![
+            issue_in("TEST-1", &review),
+            issue_in("TEST-2", &todo),
+            issue_in("TEST-3", &progress),
+        ];
+
+        let by_type = columns(&issues, GroupBy::StateType);
+        let titles: Vec<&str> = by_type.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, ["Backlog", "Todo", "In Progress", "Done"]);
+        assert_eq!(by_type[2].issues.len(), 2);
+
+        let by_name = columns(&issues, GroupBy::StateName);
+        let titles: Vec<&str> = by_name.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, ["Todo", "In Progress", "In Review"]);
+    }
+
+    #[test]
+    fn test_board() {
+        let backlog = make_state("backlog-id", "Backlog", "backlog", 0.0);
+        let todo = make_state("todo-id", "Todo", "unstarted", 0.0);
+        let progress = make_state("progress-id", "In Progress", "started", 1.0);
+        let done = make_state("done-id", "Done", "completed", 0.0);
+
+        let issue_list = MyIssuesWidget::default();
+        {
+            let mut state = issue_list.state.write().unwrap();
+            state.selected_view_id = String::from("my_issues");
+            state.issue_map.insert(
+                String::from("my_issues"),
+                vec![
+                    issue_in("TEST-1", &progress),
+                    issue_in("TEST-2", &todo),
+                    issue_in("TEST-3", &todo),
+                ],
+            );
+        }
+        let board = BoardWidget {
+            visible: true,
+            issue_list,
+            ..Default::default()
+        };
+        board
+            .state
+            .write()
+            .unwrap()
+            .team_states
+            .insert(String::new(), vec![backlog, todo, progress.clone(), done]);
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 14)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&board, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        board.handle_event(&create_key_event(KeyCode::Char('l')));
+        board.handle_event(&create_key_event(KeyCode::Char('j')));
+        assert_eq!(board.selected_issue().unwrap().identifier, "TEST-3");
+
+        let ev = board.handle_event(&create_key_event(KeyCode::Char('L')));
+        match ev {
+            LtEvent::SetIssueState(issue_id, picked) => {
+                assert_eq!(issue_id, "TEST-3-id");
+                assert_eq!(picked.id, progress.id);
+            }
+            _ => panic!("expected SetIssueState, got {:?}", ev),
+        }
+
+        // there is nothing left of the first column
+        board.handle_event(&create_key_event(KeyCode::Char('h')));
+        board.handle_event(&create_key_event(KeyCode::Char('h')));
+        assert_eq!(
+            board.handle_event(&create_key_event(KeyCode::Char('H'))),
+            LtEvent::None
+        );
+    }
+}
@@ -0,0 +1,379 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use crossterm::event::{Event, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{
+        Axis, Block, Chart, Dataset, GraphType, List, ListItem, ListState, Paragraph,
+        StatefulWidget, Widget,
+    },
+};
+
+use crate::{
+    LtEvent,
+    api::LinearClient,
+    config::{Action, Keymap},
+    queries::{
+        CyclesQuery,
+        cycles_query::{self, CycleFragment},
+    },
+    widgets::list_tab::{HIGHLIGHT_SYMBOL, ListTab, SELECTED_STYLE},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum CycleStatus {
+    Current,
+    Upcoming,
+    Past,
+}
+
+impl CycleStatus {
+    fn of(cycle: &CycleFragment, now: DateTime<Utc>) -> Self {
+        let date = |date: &str| DateTime::parse_from_rfc3339(date).ok();
+        match (date(&cycle.starts_at), date(&cycle.ends_at)) {
+            (Some(starts_at), _) if now < starts_at => CycleStatus::Upcoming,
+            (_, Some(ends_at)) if now < ends_at && cycle.completed_at.is_none() => {
+                CycleStatus::Current
+            }
+            _ => CycleStatus::Past,
+        }
+    }
+
+    fn label(&self) -> Span<'static> {
+        match self {
+            CycleStatus::Current => Span::from("Current ").green(),
+            CycleStatus::Upcoming => Span::from("Upcoming").blue(),
+            CycleStatus::Past => Span::from("Past    ").dark_gray(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CycleItem {
+    cycle: CycleFragment,
+    status: CycleStatus,
+}
+
+impl CycleItem {
+    fn title(&self) -> String {
+        match &self.cycle.name {
+            Some(name) if !name.is_empty() => name.clone(),
+            _ => format!("Cycle {}", self.cycle.number),
+        }
+    }
+}
+
+// "Jul 7"
+fn short_date(date: &str) -> String {
+    DateTime::parse_from_rfc3339(date)
+        .map(|date| date.format("%b %-d").to_string())
+        .unwrap_or_default()
+}
+
+// the cycles of all the viewer's teams, the current ones first, then the upcoming ones from the
+// soonest and the past ones from the latest
+fn cycle_items(data: cycles_query::ResponseData, now: DateTime<Utc>) -> Vec<CycleItem> {
+    let mut items: Vec<CycleItem> = data
+        .viewer
+        .teams
+        .nodes
+        .into_iter()
+        .flat_map(|team| team.cycles.nodes)
+        .map(|cycle| CycleItem {
+            status: CycleStatus::of(&cycle, now),
+            cycle,
+        })
+        .collect();
+    items.sort_by(|a, b| {
+        a.status.cmp(&b.status).then_with(|| match a.status {
+            CycleStatus::Past => b.cycle.starts_at.cmp(&a.cycle.starts_at),
+            _ => a.cycle.starts_at.cmp(&b.cycle.starts_at),
+        })
+    });
+    items
+}
+
+// scope and completed scope by day since the start of the cycle
+fn history_points(history: &[f64]) -> Vec<(f64, f64)> {
+    history
+        .iter()
+        .enumerate()
+        .map(|(day, value)| (day as f64, *value))
+        .collect()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CyclesWidget {
+    // the cycles and the burndown take the place of the issue list and the detail view
+    list: ListTab<CycleItem, ListState>,
+    client: Arc<LinearClient>,
+    keymap: Arc<Keymap>,
+}
+
+impl CyclesWidget {
+    pub fn new(client: Arc<LinearClient>, keymap: Arc<Keymap>) -> Self {
+        Self {
+            client,
+            keymap,
+            ..Default::default()
+        }
+    }
+
+    pub fn visible(&self) -> bool {
+        self.list.visible
+    }
+
+    // a cycle's issues are shown in the issue list, esc goes back to the cycles
+    pub fn in_cycle(&self) -> bool {
+        self.list.in_item
+    }
+
+    pub fn run(&self) {
+        if self.list.start_loading() {
+            tokio::spawn(self.clone().fetch());
+        }
+    }
+
+    pub fn open(&mut self) {
+        if self.list.open() {
+            self.run();
+        }
+    }
+
+    pub fn close(&mut self) {
+        self.list.close();
+    }
+
+    pub fn back(&mut self) {
+        self.list.back();
+    }
+
+    async fn fetch(self) {
+        let client = &self.client;
+        let variables = cycles_query::Variables {};
+        match client.query(CyclesQuery, variables).await {
+            Ok(data) => self.set_items(cycle_items(data, Utc::now())),
+            Err(e) => self.list.write().set_error(e.to_string()),
+        }
+    }
+
+    // the current cycle comes first, so it's the one selected at first
+    fn set_items(&self, items: Vec<CycleItem>) {
+        self.list
+            .write()
+            .set_items(items, |item| item.cycle.id.as_str());
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> LtEvent<'_> {
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            // nothing else scrolls while the cycles are shown
+            let action = match self.keymap.action(key) {
+                Some(Action::ScrollDown) => Some(Action::Down),
+                Some(Action::ScrollUp) => Some(Action::Up),
+                action => action,
+            };
+            if self.list.handle_action(action) {
+                self.run();
+            }
+            if action == Some(Action::Select) {
+                let cycle_id = self
+                    .list
+                    .read()
+                    .selected()
+                    .map(|item| item.cycle.id.clone());
+                if let Some(cycle_id) = cycle_id {
+                    self.list.enter();
+                    return LtEvent::OpenCycle(cycle_id);
+                }
+            }
+        }
+        LtEvent::None
+    }
+}
+
+fn cycle_item(item: &CycleItem) -> ListItem<'static> {
+    ListItem::new(Line::from(vec![
+        item.status.label(),
+        Span::from(format!(" {} ", item.cycle.team.key)).dark_gray(),
+        Span::from(item.title()).bold(),
+        Span::from(format!(
+            " {} – {}",
+            short_date(&item.cycle.starts_at),
+            short_date(&item.cycle.ends_at)
+        ))
+        .dark_gray(),
+    ]))
+}
+
+fn render_burndown(item: &CycleItem, area: Rect, buf: &mut Buffer) {
+    let block = Block::bordered().title(Line::from(vec![
+        Span::from(format!(" {} ", item.title())).bold(),
+        Span::from(format!("{:.0}% done ", item.cycle.progress * 100.0)).dark_gray(),
+    ]));
+    let scope = history_points(&item.cycle.scope_history);
+    let completed = history_points(&item.cycle.completed_scope_history);
+    if scope.is_empty() {
+        Paragraph::new("No history yet".dark_gray())
+            .block(block)
+            .render(area, buf);
+        return;
+    }
+
+    let days = match (
+        DateTime::parse_from_rfc3339(&item.cycle.starts_at),
+        DateTime::parse_from_rfc3339(&item.cycle.ends_at),
+    ) {
+        (Ok(starts_at), Ok(ends_at)) => (ends_at - starts_at).num_days() as f64,
+        _ => 0.0,
+    }
+    .max(scope.len() as f64 - 1.0)
+    .max(1.0);
+    let max_scope = scope
+        .iter()
+        .map(|(_, value)| *value)
+        .fold(1.0, f64::max)
+        .ceil();
+    let datasets = vec![
+        Dataset::default()
+            .name("Scope")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::new().fg(Color::Blue))
+            .data(&scope),
+        Dataset::default()
+            .name("Completed")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::new().fg(Color::Green))
+            .data(&completed),
+    ];
+    // the legend is hidden in small charts by default
+    Chart::new(datasets)
+        .block(block)
+        .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)))
+        .x_axis(
+            Axis::default()
+                .style(Style::new().dark_gray())
+                .bounds([0.0, days])
+                .labels([
+                    short_date(&item.cycle.starts_at),
+                    short_date(&item.cycle.ends_at),
+                ]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::new().dark_gray())
+                .bounds([0.0, max_scope])
+                .labels([String::from("0"), format!("{}", max_scope)]),
+        )
+        .render(area, buf);
+}
+
+impl Widget for &CyclesWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut state = self.list.write();
+        let block = Block::bordered().title_bottom(Line::from(vec![
+            Span::from(format!(
+                " <{}/{}> ",
+                self.keymap.label(Action::Down),
+                self.keymap.label(Action::Up)
+            ))
+            .blue(),
+            Span::from("to select "),
+            Span::from("─"),
+            Span::from(format!(" <{}> ", self.keymap.label(Action::Select))).blue(),
+            Span::from("to show issues "),
+        ]));
+
+        let Some(mut block) = state.render_status(block, &self.keymap, area, buf) else {
+            return;
+        };
+        block = block.title(Line::from(" Cycles ").bold());
+        if state.items.is_empty() {
+            Paragraph::new("Your teams have no cycles".dark_gray())
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+
+        let horizontal = Layout::horizontal([Constraint::Percentage(45), Constraint::Fill(1)]);
+        let [list_area, chart_area] = horizontal.areas(area);
+        if let Some(item) = state.selected() {
+            render_burndown(item, chart_area, buf);
+        }
+        let rows: Vec<ListItem> = state.items.iter().map(cycle_item).collect();
+        let list = List::new(rows)
+            .highlight_style(SELECTED_STYLE)
+            .highlight_symbol(HIGHLIGHT_SYMBOL)
+            .block(block);
+        StatefulWidget::render(list, list_area, buf, &mut state.selection);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::{DateTime, Utc};
+    use crossterm::event::KeyCode;
+    use insta::assert_snapshot;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use crate::{
+        LtEvent,
+        queries::{CyclesQuery, cycles_query},
+        stub_server::StubServer,
+        widgets::create_key_event,
+    };
+
+    use super::{CycleStatus, CyclesWidget, cycle_items};
+
+    #[tokio::test]
+    async fn test_cycles() {
+        let server = StubServer::start();
+        let mut app = CyclesWidget::new(server.client(), Arc::default());
+        let data = server
+            .client()
+            .query(CyclesQuery, cycles_query::Variables {})
+            .await
+            .unwrap();
+        // the fixture's cycles are current, upcoming and past as of this date
+        let now = DateTime::parse_from_rfc3339("2025-07-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let items = cycle_items(data, now);
+        let statuses: Vec<(f64, CycleStatus)> = items
+            .iter()
+            .map(|item| (item.cycle.number, item.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                (12.0, CycleStatus::Current),
+                (13.0, CycleStatus::Upcoming),
+                (11.0, CycleStatus::Past),
+                (10.0, CycleStatus::Past)
+            ]
+        );
+        app.set_items(items);
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 14)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        assert_eq!(
+            app.handle_event(&create_key_event(KeyCode::Enter)),
+            LtEvent::OpenCycle(String::from("cycle-12"))
+        );
+        assert!(!app.visible() && app.in_cycle());
+    }
+}
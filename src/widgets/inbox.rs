@@ -0,0 +1,361 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use crossterm::event::{Event, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Stylize,
+    text::{Line, Span, Text},
+    widgets::{Block, List, ListItem, ListState, Paragraph, StatefulWidget, Widget},
+};
+
+use crate::{
+    IssueFragment, LtEvent,
+    api::LinearClient,
+    config::{Action, Keymap},
+    queries::{
+        NotificationArchiveMutation, NotificationUpdateMutation, NotificationsQuery,
+        notification_archive_mutation, notification_update_mutation,
+        notifications_query::{self, NotificationFragment, NotificationFragmentOn},
+    },
+    widgets::list_tab::{HIGHLIGHT_SYMBOL, ListTab, SELECTED_STYLE},
+};
+
+#[derive(Debug, Clone)]
+struct InboxItem {
+    id: String,
+    type_: String,
+    created_at: String,
+    read_at: Option<String>,
+    actor: Option<String>,
+    // only issue notifications are listed
+    issue: IssueFragment,
+}
+
+impl InboxItem {
+    fn from_fragment(notification: NotificationFragment) -> Option<Self> {
+        let NotificationFragmentOn::IssueNotification(on) = notification.on else {
+            return None;
+        };
+        Some(Self {
+            id: notification.id,
+            type_: notification.type_,
+            created_at: notification.created_at,
+            read_at: notification.read_at,
+            actor: notification.actor.map(|actor| {
+                if actor.is_me {
+                    String::from("You")
+                } else {
+                    actor.display_name
+                }
+            }),
+            issue: on.issue.into(),
+        })
+    }
+
+    fn is_unread(&self) -> bool {
+        self.read_at.is_none()
+    }
+}
+
+// "issueAssignedToYou" reads as "Assigned to you"
+fn type_label(type_: &str) -> String {
+    let name = type_.strip_prefix("issue").unwrap_or(type_);
+    let mut label = String::new();
+    for c in name.chars() {
+        if c.is_uppercase() && !label.is_empty() {
+            label.push(' ');
+            label.extend(c.to_lowercase());
+        } else {
+            label.push(c);
+        }
+    }
+    let mut chars = label.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => label,
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct InboxWidget {
+    list: ListTab<InboxItem, ListState>,
+    client: Arc<LinearClient>,
+    keymap: Arc<Keymap>,
+}
+
+impl InboxWidget {
+    pub fn new(client: Arc<LinearClient>, keymap: Arc<Keymap>) -> Self {
+        Self {
+            client,
+            keymap,
+            ..Default::default()
+        }
+    }
+
+    pub fn visible(&self) -> bool {
+        self.list.visible
+    }
+
+    pub fn run(&self) {
+        if self.list.start_loading() {
+            tokio::spawn(self.clone().fetch());
+        }
+    }
+
+    // notifications keep coming in, so the inbox is fetched again whenever it's opened
+    pub fn open(&mut self) {
+        self.list.open();
+        self.run();
+    }
+
+    pub fn close(&mut self) {
+        self.list.close();
+    }
+
+    async fn fetch(self) {
+        let client = &self.client;
+        let variables = notifications_query::Variables {};
+        match client.query(NotificationsQuery, variables).await {
+            // items marked read here stay listed until the next fetch
+            Ok(data) => {
+                let items: Vec<InboxItem> = data
+                    .notifications
+                    .nodes
+                    .into_iter()
+                    .filter_map(InboxItem::from_fragment)
+                    .filter(InboxItem::is_unread)
+                    .collect();
+                self.list.write().set_items(items, |item| item.id.as_str());
+            }
+            Err(e) => self.list.write().set_error(e.to_string()),
+        }
+    }
+
+    pub fn unread_count(&self) -> usize {
+        let state = self.list.read();
+        state.items.iter().filter(|item| item.is_unread()).count()
+    }
+
+    pub fn selected_issue(&self) -> Option<IssueFragment> {
+        let state = self.list.read();
+        state.selected().map(|item| item.issue.clone())
+    }
+
+    fn mark_read(&self) {
+        let read_at = Utc::now().to_rfc3339();
+        let mut state = self.list.write();
+        let Some(index) = state.selection.selected() else {
+            return;
+        };
+        let Some(item) = state.items.get_mut(index) else {
+            return;
+        };
+        if !item.is_unread() {
+            return;
+        }
+        item.read_at = Some(read_at.clone());
+        let id = item.id.clone();
+        state.notice = None;
+        tokio::spawn(self.clone().update_read_at(id, read_at));
+    }
+
+    async fn update_read_at(self, id: String, read_at: String) {
+        let client = &self.client;
+        let variables = notification_update_mutation::Variables {
+            id: id.clone(),
+            input: notification_update_mutation::NotificationUpdateInput {
+                read_at: Some(read_at),
+                ..Default::default()
+            },
+        };
+        if let Err(e) = client.query(NotificationUpdateMutation, variables).await {
+            let mut state = self.list.write();
+            if let Some(item) = state.items.iter_mut().find(|item| item.id == id) {
+                item.read_at = None;
+            }
+            state.notice = Some(format!("Couldn't mark as read: {}", e));
+        }
+    }
+
+    fn archive(&self) {
+        let mut state = self.list.write();
+        let Some(index) = state.selection.selected() else {
+            return;
+        };
+        if index >= state.items.len() {
+            return;
+        }
+        let item = state.items.remove(index);
+        let selected = if state.items.is_empty() {
+            None
+        } else {
+            Some(index.min(state.items.len() - 1))
+        };
+        state.selection.select(selected);
+        state.notice = None;
+        tokio::spawn(self.clone().archive_item(index, item));
+    }
+
+    async fn archive_item(self, index: usize, item: InboxItem) {
+        let client = &self.client;
+        let variables = notification_archive_mutation::Variables {
+            id: item.id.clone(),
+        };
+        if let Err(e) = client.query(NotificationArchiveMutation, variables).await {
+            let mut state = self.list.write();
+            let index = index.min(state.items.len());
+            state.items.insert(index, item);
+            state.notice = Some(format!("Couldn't archive: {}", e));
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> LtEvent<'_> {
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            let action = self.keymap.action(key);
+            if self.list.handle_action(action) {
+                self.run();
+            }
+            match action {
+                Some(Action::Down | Action::Up) => return LtEvent::SelectIssue,
+                Some(Action::MarkRead) => self.mark_read(),
+                Some(Action::Archive) => {
+                    self.archive();
+                    return LtEvent::SelectIssue;
+                }
+                Some(Action::OpenUrl) => {
+                    if let Some(issue) = self.selected_issue() {
+                        let _ = open::that(&issue.url);
+                    }
+                }
+                _ => {}
+            }
+        }
+        LtEvent::None
+    }
+}
+
+fn inbox_item(item: &InboxItem) -> ListItem<'static> {
+    let unread = item.is_unread();
+    let marker = if unread {
+        Span::from("● ").blue()
+    } else {
+        Span::from("  ")
+    };
+    let mut title = Line::from(vec![
+        marker,
+        Span::from(item.issue.identifier.clone()).bold(),
+        Span::from(" "),
+        Span::from(item.issue.title.clone()),
+    ]);
+    let created_at = DateTime::parse_from_rfc3339(&item.created_at)
+        .map(|date| date.format("%m/%d").to_string())
+        .unwrap_or_default();
+    let mut details = vec![
+        Span::from("  "),
+        Span::from(type_label(&item.type_)).yellow(),
+    ];
+    if let Some(actor) = &item.actor {
+        details.push(Span::from(format!(" · {}", actor)));
+    }
+    details.push(Span::from(format!(" · {}", created_at)).dark_gray());
+    let mut details = Line::from(details);
+    if !unread {
+        title = title.dark_gray();
+        details = details.dark_gray();
+    }
+    ListItem::new(Text::from(vec![title, details]))
+}
+
+impl Widget for &InboxWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut state = self.list.write();
+        let block = Block::bordered().title_bottom(Line::from(vec![
+            Span::from(format!(" <{}> ", self.keymap.label(Action::MarkRead))).blue(),
+            Span::from("to mark read "),
+            Span::from("─"),
+            Span::from(format!(" <{}> ", self.keymap.label(Action::Archive))).blue(),
+            Span::from("to archive"),
+        ]));
+
+        let Some(mut block) = state.render_status(block, &self.keymap, area, buf) else {
+            return;
+        };
+        let unread = state.items.iter().filter(|item| item.is_unread()).count();
+        block = block.title(Line::from(format!(" {} unread ", unread)).dark_gray());
+        if state.items.is_empty() {
+            Paragraph::new("Inbox zero".dark_gray())
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+
+        let rows: Vec<ListItem> = state.items.iter().map(inbox_item).collect();
+        let list = List::new(rows)
+            .highlight_style(SELECTED_STYLE)
+            .highlight_symbol(HIGHLIGHT_SYMBOL)
+            .block(block);
+        StatefulWidget::render(list, area, buf, &mut state.selection);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crossterm::event::KeyCode;
+    use insta::assert_snapshot;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use crate::{stub_server::StubServer, widgets::create_key_event};
+
+    use super::{InboxWidget, type_label};
+
+    #[test]
+    fn test_type_label() {
+        assert_eq!(type_label("issueAssignedToYou"), "Assigned to you");
+        assert_eq!(type_label("issueNewComment"), "New comment");
+        assert_eq!(type_label("issueStatusChanged"), "Status changed");
+        assert_eq!(type_label(""), "");
+    }
+
+    #[tokio::test]
+    async fn test_inbox() {
+        let server = StubServer::start();
+        let mut app = InboxWidget::new(server.client(), Arc::default());
+        app.clone().fetch().await;
+
+        // only unread notifications are listed, and project notifications have no issue to show
+        assert_eq!(app.list.read().items.len(), 2);
+        assert_eq!(app.unread_count(), 2);
+        assert_eq!(app.selected_issue().unwrap().identifier, "LT-5");
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 10)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        app.handle_event(&create_key_event(KeyCode::Char('j')));
+        assert_eq!(app.selected_issue().unwrap().identifier, "LT-7");
+        app.handle_event(&create_key_event(KeyCode::Char('x')));
+        assert_eq!(app.unread_count(), 1);
+
+        // a failed archive puts the notification back
+        server.respond_with(
+            "NotificationArchiveMutation",
+            r#"{"errors": [{"message": "Entity not found"}]}"#,
+        );
+        let item = app.list.write().items.remove(0);
+        app.clone().archive_item(0, item).await;
+        let state = app.list.read();
+        assert_eq!(state.items[0].id, "notification-1");
+        assert_eq!(
+            state.notice.as_deref(),
+            Some("Couldn't archive: Entity not found")
+        );
+    }
+}
@@ -0,0 +1,610 @@
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph, Widget, Wrap},
+};
+use tui_input::Input;
+use tui_input::backend::crossterm::EventHandler;
+
+use crate::{
+    IssueFragment, LoadingState, LtEvent,
+    api::LinearClient,
+    iconmap,
+    queries::{
+        IssueFormQuery,
+        issue_create_mutation::IssueCreateInput,
+        issue_form_query::{self, TeamOptionsFragment},
+    },
+    widgets::popup_area,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+enum FormField {
+    #[default]
+    Team,
+    Title,
+    Description,
+    Priority,
+    Assignee,
+    Labels,
+    Project,
+}
+
+const FIELDS: [FormField; 7] = [
+    FormField::Team,
+    FormField::Title,
+    FormField::Description,
+    FormField::Priority,
+    FormField::Assignee,
+    FormField::Labels,
+    FormField::Project,
+];
+
+const PRIORITIES: [(i64, &str); 5] = [
+    (0, "No priority"),
+    (1, "Urgent"),
+    (2, "High"),
+    (3, "Normal"),
+    (4, "Low"),
+];
+
+#[derive(Debug, Default)]
+struct IssueFormWidgetState {
+    loading_state: LoadingState,
+    teams: Vec<TeamOptionsFragment>,
+    // team to preselect once the options arrive
+    preferred_team_id: Option<String>,
+    focus: FormField,
+    team_index: usize,
+    title: Input,
+    description: Input,
+    priority_index: usize,
+    assignee_index: Option<usize>,
+    label_cursor: usize,
+    label_ids: Vec<String>,
+    project_index: Option<usize>,
+    error: Option<String>,
+    // set when creating the issue failed, so the form shows up again with what was typed
+    reopen: bool,
+}
+
+impl IssueFormWidgetState {
+    fn team(&self) -> Option<&TeamOptionsFragment> {
+        self.teams.get(self.team_index)
+    }
+
+    // picks the defaults that depend on the team: yourself as assignee, no labels, no project
+    fn reset_team_fields(&mut self) {
+        self.assignee_index = self
+            .team()
+            .and_then(|team| team.members.nodes.iter().position(|member| member.is_me));
+        self.label_cursor = 0;
+        self.label_ids.clear();
+        self.project_index = None;
+    }
+}
+
+// steps an optional index through `len` options, where `None` comes before the first option
+fn cycle_optional(index: Option<usize>, len: usize, forward: bool) -> Option<usize> {
+    match (index, forward) {
+        (None, true) if len > 0 => Some(0),
+        (None, false) if len > 0 => Some(len - 1),
+        (Some(i), true) if i + 1 < len => Some(i + 1),
+        (Some(i), false) if i > 0 => Some(i - 1),
+        _ => None,
+    }
+}
+
+fn cycle(index: usize, len: usize, forward: bool) -> usize {
+    if len == 0 {
+        0
+    } else if forward {
+        (index + 1) % len
+    } else {
+        (index + len - 1) % len
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IssueFormWidget {
+    state: Arc<RwLock<IssueFormWidgetState>>,
+    client: Arc<LinearClient>,
+    pub visible: bool,
+}
+
+impl IssueFormWidget {
+    pub fn new(client: Arc<LinearClient>) -> Self {
+        Self {
+            client,
+            ..Default::default()
+        }
+    }
+
+    // `current_issue` is used to default the team to the one you're looking at
+    pub fn open(&mut self, current_issue: Option<IssueFragment>) {
+        self.visible = true;
+        let mut state = self.state.write().unwrap();
+        // team options are kept between openings, everything else starts fresh
+        let teams = std::mem::take(&mut state.teams);
+        let loaded = state.loading_state == LoadingState::Loaded;
+        *state = IssueFormWidgetState {
+            preferred_team_id: current_issue.map(|issue| issue.team.id),
+            ..Default::default()
+        };
+        drop(state);
+        if loaded {
+            self.set_teams(teams);
+        } else {
+            self.state.write().unwrap().loading_state = LoadingState::Loading;
+            tokio::spawn(self.clone().fetch());
+        }
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+    }
+
+    // fills the form in again with an issue that couldn't be created, it shows up again with the
+    // next frame
+    pub fn restore(&self, input: &IssueCreateInput, error: String) {
+        let mut state = self.state.write().unwrap();
+        if let Some(team_index) = state.teams.iter().position(|team| team.id == input.team_id) {
+            let team = &state.teams[team_index];
+            let assignee_index = input.assignee_id.as_ref().and_then(|id| {
+                team.members
+                    .nodes
+                    .iter()
+                    .position(|member| member.id == *id)
+            });
+            let project_index = input.project_id.as_ref().and_then(|id| {
+                team.projects
+                    .nodes
+                    .iter()
+                    .position(|project| project.id == *id)
+            });
+            state.team_index = team_index;
+            state.reset_team_fields();
+            state.assignee_index = assignee_index;
+            state.project_index = project_index;
+            state.label_ids = input.label_ids.clone().unwrap_or_default();
+        }
+        state.title = Input::new(input.title.clone().unwrap_or_default());
+        state.description = Input::new(input.description.clone().unwrap_or_default());
+        state.priority_index = PRIORITIES
+            .iter()
+            .position(|(priority, _)| Some(*priority) == input.priority)
+            .unwrap_or(0);
+        state.error = Some(error);
+        state.reopen = true;
+    }
+
+    pub fn reopen_if_failed(&mut self) {
+        if std::mem::take(&mut self.state.write().unwrap().reopen) {
+            self.visible = true;
+        }
+    }
+
+    async fn fetch(self) {
+        let client = &self.client;
+        let variables = issue_form_query::Variables {};
+        match client.query(IssueFormQuery, variables).await {
+            Ok(data) => self.set_teams(data.teams.nodes),
+            Err(e) => {
+                self.state.write().unwrap().loading_state = LoadingState::Error(e.to_string());
+            }
+        }
+    }
+
+    fn set_teams(&self, teams: Vec<TeamOptionsFragment>) {
+        let mut state = self.state.write().unwrap();
+        state.team_index = teams
+            .iter()
+            .position(|team| Some(&team.id) == state.preferred_team_id.as_ref())
+            .unwrap_or(0);
+        state.teams = teams;
+        state.reset_team_fields();
+        state.loading_state = LoadingState::Loaded;
+    }
+
+    fn build_input(state: &IssueFormWidgetState) -> Result<IssueCreateInput, String> {
+        let team = state.team().ok_or("Pick a team first")?;
+        let title = state.title.value().trim();
+        if title.is_empty() {
+            return Err("Title is required".into());
+        }
+        let description = state.description.value().trim();
+        let priority = PRIORITIES[state.priority_index].0;
+        Ok(IssueCreateInput {
+            team_id: team.id.clone(),
+            title: Some(title.to_string()),
+            description: (!description.is_empty()).then(|| description.to_string()),
+            priority: (priority > 0).then_some(priority),
+            assignee_id: state
+                .assignee_index
+                .map(|index| team.members.nodes[index].id.clone()),
+            label_ids: (!state.label_ids.is_empty()).then(|| state.label_ids.clone()),
+            project_id: state
+                .project_index
+                .map(|index| team.projects.nodes[index].id.clone()),
+            ..Default::default()
+        })
+    }
+
+    fn change_choice(state: &mut IssueFormWidgetState, forward: bool) {
+        let Some(team) = state.team() else {
+            return;
+        };
+        let members = team.members.nodes.len();
+        let labels = team.labels.nodes.len();
+        let projects = team.projects.nodes.len();
+        match state.focus {
+            FormField::Team => {
+                state.team_index = cycle(state.team_index, state.teams.len(), forward);
+                state.reset_team_fields();
+            }
+            FormField::Priority => {
+                state.priority_index = cycle(state.priority_index, PRIORITIES.len(), forward);
+            }
+            FormField::Assignee => {
+                state.assignee_index = cycle_optional(state.assignee_index, members, forward);
+            }
+            FormField::Labels => {
+                state.label_cursor = cycle(state.label_cursor, labels, forward);
+            }
+            FormField::Project => {
+                state.project_index = cycle_optional(state.project_index, projects, forward);
+            }
+            FormField::Title | FormField::Description => {}
+        }
+    }
+
+    fn toggle_label(state: &mut IssueFormWidgetState) {
+        let Some(label) = state
+            .team()
+            .and_then(|team| team.labels.nodes.get(state.label_cursor))
+            .map(|label| label.id.clone())
+        else {
+            return;
+        };
+        match state.label_ids.iter().position(|id| *id == label) {
+            Some(index) => {
+                state.label_ids.remove(index);
+            }
+            None => state.label_ids.push(label),
+        }
+    }
+
+    fn move_focus(state: &mut IssueFormWidgetState, forward: bool) {
+        let index = FIELDS.iter().position(|f| *f == state.focus).unwrap_or(0);
+        state.focus = FIELDS[cycle(index, FIELDS.len(), forward)];
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> LtEvent<'_> {
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            if key.code == KeyCode::Esc {
+                self.close();
+                return LtEvent::None;
+            }
+            let mut state = self.state.write().unwrap();
+            if state.loading_state != LoadingState::Loaded {
+                return LtEvent::None;
+            }
+            state.error = None;
+            match (state.focus, key.code) {
+                (_, KeyCode::Tab | KeyCode::Down) => Self::move_focus(&mut state, true),
+                (_, KeyCode::BackTab | KeyCode::Up) => Self::move_focus(&mut state, false),
+                (_, KeyCode::Enter) => match Self::build_input(&state) {
+                    Ok(input) => {
+                        drop(state);
+                        self.close();
+                        return LtEvent::CreateIssue(Box::new(input));
+                    }
+                    Err(e) => state.error = Some(e),
+                },
+                (FormField::Title, _) => {
+                    state.title.handle_event(event);
+                }
+                (FormField::Description, _) => {
+                    state.description.handle_event(event);
+                }
+                (FormField::Labels, KeyCode::Char(' ')) => Self::toggle_label(&mut state),
+                (_, KeyCode::Left | KeyCode::Char('h')) => Self::change_choice(&mut state, false),
+                (_, KeyCode::Right | KeyCode::Char('l')) => Self::change_choice(&mut state, true),
+                _ => {}
+            }
+        }
+        LtEvent::None
+    }
+}
+
+fn choice(value: Vec<Span<'static>>, focused: bool) -> Vec<Span<'static>> {
+    if !focused {
+        return value;
+    }
+    let mut spans = vec![Span::from("‹ ").blue()];
+    spans.extend(value);
+    spans.push(Span::from(" ›").blue());
+    spans
+}
+
+impl Widget for &IssueFormWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if !self.visible {
+            return;
+        }
+        let area = popup_area(area, 70, 70);
+        Clear.render(area, buf);
+
+        let state = self.state.read().unwrap();
+        let mut block = Block::bordered()
+            .title(Line::from(" New issue ").bold())
+            .title_bottom(Line::from(vec![
+                Span::from(" <tab> ").blue(),
+                Span::from("next field "),
+                Span::from("─"),
+                Span::from(" <←/→> ").blue(),
+                Span::from("choose "),
+                Span::from("─"),
+                Span::from(" <enter> ").blue(),
+                Span::from("create "),
+                Span::from("─"),
+                Span::from(" <esc> ").blue(),
+                Span::from("cancel "),
+            ]));
+        if let Some(error) = &state.error {
+            block = block.title(Line::from(format!(" {} ", error).red().bold()).right_aligned());
+        }
+
+        match &state.loading_state {
+            LoadingState::Loaded => {}
+            LoadingState::Error(e) => {
+                Paragraph::new(e.clone().red().bold())
+                    .block(block)
+                    .render(area, buf);
+                return;
+            }
+            _ => {
+                Paragraph::new("Loading…").block(block).render(area, buf);
+                return;
+            }
+        }
+        let Some(team) = state.team() else {
+            Paragraph::new("No teams found".red())
+                .block(block)
+                .render(area, buf);
+            return;
+        };
+
+        let text_value = |input: &Input, focused: bool| {
+            if focused {
+                vec![Span::from(input.value().to_owned() + "|").yellow()]
+            } else {
+                vec![Span::from(input.value().to_owned())]
+            }
+        };
+
+        let mut lines = vec![];
+        for field in FIELDS {
+            let focused = state.focus == field;
+            let (label, value) = match field {
+                FormField::Team => (
+                    "Team",
+                    choice(
+                        vec![Span::from(format!("{} ({})", team.name, team.key))],
+                        focused,
+                    ),
+                ),
+                FormField::Title => ("Title", text_value(&state.title, focused)),
+                FormField::Description => ("Description", text_value(&state.description, focused)),
+                FormField::Priority => {
+                    let (priority, label) = PRIORITIES[state.priority_index];
+                    (
+                        "Priority",
+                        choice(
+                            vec![
+                                Span::from(iconmap::p_to_nf(priority as f64)),
+                                Span::from(label),
+                            ],
+                            focused,
+                        ),
+                    )
+                }
+                FormField::Assignee => {
+                    let assignee = match state.assignee_index {
+                        Some(index) if team.members.nodes[index].is_me => "You".to_string(),
+                        Some(index) => team.members.nodes[index].display_name.clone(),
+                        None => "Unassigned".to_string(),
+                    };
+                    ("Assignee", choice(vec![Span::from(assignee)], focused))
+                }
+                FormField::Labels => {
+                    let mut spans = vec![];
+                    for (index, label) in team.labels.nodes.iter().enumerate() {
+                        let checked = state.label_ids.contains(&label.id);
+                        let mut span = Span::from(format!(
+                            "[{}] {}",
+                            if checked { "x" } else { " " },
+                            label.name
+                        ))
+                        .fg(Color::from_str(&label.color).unwrap_or(Color::White));
+                        if focused && index == state.label_cursor {
+                            span = span.add_modifier(Modifier::REVERSED);
+                        }
+                        spans.push(span);
+                        spans.push(Span::from(" "));
+                    }
+                    if spans.is_empty() {
+                        spans.push(Span::from("No labels").dark_gray());
+                    } else if focused {
+                        spans.push(Span::from("<space> to toggle").dark_gray());
+                    }
+                    ("Labels", spans)
+                }
+                FormField::Project => {
+                    let project = match state.project_index {
+                        Some(index) => {
+                            let project = &team.projects.nodes[index];
+                            let color = Color::from_str(&project.color).unwrap_or(Color::White);
+                            vec![
+                                Span::from(iconmap::ico_to_nf(
+                                    project.icon.as_deref().unwrap_or("NN"),
+                                ))
+                                .fg(color),
+                                Span::from(project.name.clone()).fg(color),
+                            ]
+                        }
+                        None => vec![Span::from("No project")],
+                    };
+                    ("Project", choice(project, focused))
+                }
+            };
+            let mut spans = vec![if focused {
+                Span::from(format!("> {:<13}", label)).cyan().bold()
+            } else {
+                Span::from(format!("  {:<13}", label))
+            }];
+            spans.extend(value);
+            lines.push(Line::from(spans));
+            lines.push(Line::from(""));
+        }
+
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+    use insta::assert_snapshot;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use crate::{
+        LtEvent,
+        queries::issue_form_query::{
+            TeamOptionsFragment, TeamOptionsFragmentLabels, TeamOptionsFragmentLabelsNodes,
+            TeamOptionsFragmentMembers, TeamOptionsFragmentMembersNodes,
+            TeamOptionsFragmentProjects, TeamOptionsFragmentProjectsNodes,
+        },
+        widgets::create_key_event,
+    };
+
+    use super::IssueFormWidget;
+
+    fn make_team(id: &str, key: &str) -> TeamOptionsFragment {
+        TeamOptionsFragment {
+            id: id.into(),
+            name: format!("Team {}", key),
+            key: key.into(),
+            members: TeamOptionsFragmentMembers {
+                nodes: vec![
+                    TeamOptionsFragmentMembersNodes {
+                        id: "other-id".into(),
+                        display_name: "Someone Else".into(),
+                        is_me: false,
+                    },
+                    TeamOptionsFragmentMembersNodes {
+                        id: "me-id".into(),
+                        display_name: "Me".into(),
+                        is_me: true,
+                    },
+                ],
+            },
+            labels: TeamOptionsFragmentLabels {
+                nodes: vec![
+                    TeamOptionsFragmentLabelsNodes {
+                        id: "bug-id".into(),
+                        name: "Bug".into(),
+                        color: "#FA0FA0".into(),
+                    },
+                    TeamOptionsFragmentLabelsNodes {
+                        id: "feature-id".into(),
+                        name: "Feature".into(),
+                        color: "#0FA0FA".into(),
+                    },
+                ],
+            },
+            projects: TeamOptionsFragmentProjects {
+                nodes: vec![TeamOptionsFragmentProjectsNodes {
+                    id: "project-id".into(),
+                    name: "Test Project".into(),
+                    icon: Some("Subgroup".into()),
+                    color: "#FA0FA0".into(),
+                }],
+            },
+        }
+    }
+
+    #[test]
+    fn test_create_issue() {
+        let mut app = IssueFormWidget {
+            visible: true,
+            ..Default::default()
+        };
+        app.state.write().unwrap().preferred_team_id = Some("team-b".into());
+        app.set_teams(vec![make_team("team-a", "AAA"), make_team("team-b", "BBB")]);
+
+        // the title is required
+        let ev = app.handle_event(&create_key_event(KeyCode::Enter));
+        assert_eq!(ev, LtEvent::None);
+        assert!(app.visible);
+
+        app.handle_event(&create_key_event(KeyCode::Tab));
+        for c in "New bug".chars() {
+            app.handle_event(&create_key_event(KeyCode::Char(c)));
+        }
+        // priority: Urgent
+        app.handle_event(&create_key_event(KeyCode::Tab));
+        app.handle_event(&create_key_event(KeyCode::Tab));
+        app.handle_event(&create_key_event(KeyCode::Right));
+        // labels: Feature
+        app.handle_event(&create_key_event(KeyCode::Tab));
+        app.handle_event(&create_key_event(KeyCode::Tab));
+        app.handle_event(&create_key_event(KeyCode::Right));
+        app.handle_event(&create_key_event(KeyCode::Char(' ')));
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 24)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        let input = match app.handle_event(&create_key_event(KeyCode::Enter)) {
+            LtEvent::CreateIssue(input) => input,
+            ev => panic!("expected CreateIssue, got {:?}", ev),
+        };
+        assert_eq!(input.team_id, "team-b");
+        assert_eq!(input.title.as_deref(), Some("New bug"));
+        assert_eq!(input.description, None);
+        assert_eq!(input.priority, Some(1));
+        assert_eq!(input.assignee_id.as_deref(), Some("me-id"));
+        assert_eq!(input.label_ids, Some(vec!["feature-id".to_string()]));
+        assert_eq!(input.project_id, None);
+        assert!(!app.visible);
+
+        // a new form in the meantime doesn't lose the issue that couldn't be created
+        app.open(None);
+        app.close();
+        app.restore(&input, String::from("Couldn't create issue: offline"));
+        app.reopen_if_failed();
+        assert!(app.visible);
+        assert_eq!(
+            app.state.read().unwrap().error.as_deref(),
+            Some("Couldn't create issue: offline")
+        );
+        match app.handle_event(&create_key_event(KeyCode::Enter)) {
+            LtEvent::CreateIssue(again) => assert_eq!(again, input),
+            ev => panic!("expected CreateIssue, got {:?}", ev),
+        }
+    }
+}
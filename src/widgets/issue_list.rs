@@ -1,137 +1,846 @@
-use std::sync::{Arc, RwLock};
+use std::{
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant},
+};
 
 use crossterm::event::{Event, KeyCode, KeyEventKind};
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
-    style::{Color, Modifier, Style, Stylize, palette::material::AMBER},
+    style::{Color, Modifier, Style, Stylize},
     text::{Line, Span, Text},
     widgets::{Block, List, ListItem, ListState, Padding, Paragraph, StatefulWidget, Widget, Wrap},
 };
 use tui_input::Input;
 use tui_input::backend::crossterm::EventHandler;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::{
-    InputMode, IssueFragment, LoadingState, LtEvent, TabChangeEvent,
+    InputMode, IssueFragment, IssueFragmentState, LoadingState, LtEvent, TabChangeEvent,
     api::LinearClient,
+    cache::{self, CachedIssues},
+    config::{Action, Keymap},
+    fuzzy::fuzzy_match,
     iconmap,
+    ordering::{GroupKey, ViewOrdering},
     queries::{
-        CustomViewQuery, MyIssuesQuery, SearchQuery, custom_view_query, custom_views_query,
+        CustomViewQuery, CustomViewUpdatedQuery, FilteredIssuesQuery, IssueChangesQuery,
+        IssueCreateMutation, IssueQuery, IssueRelationCreateMutation, IssueRelationDeleteMutation,
+        IssueUpdateMutation, MyIssuesQuery, MyIssuesUpdatedQuery, SearchQuery, custom_view_query,
+        custom_view_updated_query, custom_views_query,
+        filtered_issues_query::{
+            self, DateComparator, IDComparator, IssueFilter, NullableCycleFilter,
+            NullableProjectFilter,
+        },
+        issue_changes_query, issue_create_mutation, issue_query, issue_relation_create_mutation,
+        issue_relation_delete_mutation, issue_update_mutation,
         my_issues_query::{self},
-        search_query,
+        my_issues_updated_query, search_query,
+        workflow_states_query::WorkflowStateFragment,
     },
+    widgets::{IssueFormWidget, NewRelation, PendingDescription, Relation, SelectedIssueWidget},
 };
 
+// how many issues are requested per page
+const PAGE_SIZE: i64 = 50;
+// start loading the next page when the selection is this close to the end of the list
+const PREFETCH_THRESHOLD: usize = 5;
+// incremental syncs look back a little further than the last sync to allow for clock skew
+const SYNC_OVERLAP_SECONDS: i64 = 60;
+// how long issues that a refresh brought in or changed stay marked
+const CHANGE_MARK_DURATION: Duration = Duration::from_secs(15);
+// project and cycle views are keyed by their id after a prefix, apart from custom view ids
+const PROJECT_VIEW_PREFIX: &str = "project:";
+const CYCLE_VIEW_PREFIX: &str = "cycle:";
+
+// search results depend on the search term and project and cycle views are only opened on the
+// way through the other tabs, so they aren't worth keeping
+fn is_cached(view_id: &str) -> bool {
+    view_id != "search_results"
+        && !view_id.starts_with(PROJECT_VIEW_PREFIX)
+        && !view_id.starts_with(CYCLE_VIEW_PREFIX)
+}
+
+// project and cycle views share one query, filtered by the view's project or cycle and, when
+// only changes are wanted, by the time of the last sync. None for every other view.
+fn view_filter(view_id: &str, since: Option<String>) -> Option<IssueFilter> {
+    let id = |id: &str| IDComparator {
+        eq: Some(id.to_string()),
+        ..Default::default()
+    };
+    let mut filter = if let Some(project_id) = view_id.strip_prefix(PROJECT_VIEW_PREFIX) {
+        IssueFilter {
+            project: Box::new(Some(NullableProjectFilter {
+                id: Some(id(project_id)),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+    } else if let Some(cycle_id) = view_id.strip_prefix(CYCLE_VIEW_PREFIX) {
+        IssueFilter {
+            cycle: Box::new(Some(NullableCycleFilter {
+                id: Some(id(cycle_id)),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+    } else {
+        return None;
+    };
+    filter.updated_at = since.map(|since| DateComparator {
+        gt: Some(since),
+        ..Default::default()
+    });
+    Some(filter)
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct PageInfo {
+    pub has_next_page: bool,
+    pub end_cursor: Option<String>,
+    pub total_count: Option<usize>,
+    // the page after `end_cursor` is being fetched
+    pub loading_more: bool,
+}
+
+impl PageInfo {
+    fn describe(&self, loaded: usize) -> String {
+        match (self.total_count, self.has_next_page) {
+            (Some(total), true) => format!("{} of {} loaded", loaded, total),
+            (Some(total), false) => format!("{} of {}", loaded, total),
+            (None, true) => format!("{} loaded · more available", loaded),
+            (None, false) => format!("{} issues", loaded),
+        }
+    }
+}
+
+// what a refresh brought back: either a fresh first page, or only the issues that changed
+enum ViewUpdate {
+    Page(Vec<IssueFragment>, PageInfo),
+    Changes {
+        updated: Vec<IssueFragment>,
+        changed_ids: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IssueChange {
+    New,
+    Updated,
+}
+
+impl IssueChange {
+    fn label(&self) -> &'static str {
+        match self {
+            IssueChange::New => " new",
+            IssueChange::Updated => " updated",
+        }
+    }
+}
+
+// compares a refreshed list with the `updated_at` of the issues that were shown before
+fn find_changes(
+    previous: &HashMap<String, String>,
+    issues: &[IssueFragment],
+) -> Vec<(String, IssueChange)> {
+    issues
+        .iter()
+        .filter_map(|issue| match previous.get(&issue.id) {
+            None => Some((issue.id.clone(), IssueChange::New)),
+            Some(updated_at) if *updated_at != issue.updated_at => {
+                Some((issue.id.clone(), IssueChange::Updated))
+            }
+            Some(_) => None,
+        })
+        .collect()
+}
+
+// merges incremental changes into a cached list. `changed_ids` are the cached issues that were
+// updated since the last sync; any of them missing from `updated` have moved out of the view.
+// Updated issues keep their place in the view's order and new ones go on top.
+fn merge_changes(
+    issues: &mut Vec<IssueFragment>,
+    updated: Vec<IssueFragment>,
+    changed_ids: &[String],
+) {
+    issues.retain(|issue| {
+        !changed_ids.contains(&issue.id) || updated.iter().any(|u| u.id == issue.id)
+    });
+    let mut added = vec![];
+    for issue in updated {
+        match issues.iter_mut().find(|existing| existing.id == issue.id) {
+            Some(existing) => *existing = issue,
+            None => added.push(issue),
+        }
+    }
+    issues.splice(0..0, added);
+}
+
 #[derive(Debug, Default)]
 pub struct MyIssuesWidgetState {
     loading_state: LoadingState,
     pub list_state: ListState,
     pub selected_view_id: String,
     pub issue_map: HashMap<String, Vec<IssueFragment>>,
+    page_info: HashMap<String, PageInfo>,
+    // views with a background refresh in flight
+    refreshing: HashSet<String>,
+    // when each view was last fetched, as RFC 3339
+    synced_at: HashMap<String, String>,
+    // issues the last refreshes brought in or changed, marked until the given time
+    changes: HashMap<String, (IssueChange, Instant)>,
+    search_term: String,
+    notice: Option<String>,
+    // narrows the selected view to fuzzy matches, without a request
+    filter: String,
+    // the sort and grouping picked for each view
+    orderings: HashMap<String, ViewOrdering>,
+    // selection among the rows of a grouped list, which include the group headers
+    row_state: ListState,
+    // what `visible_issues` last worked out
+    visible: Mutex<VisibleIssues>,
+}
+
+// the positions of the selected view's issues that match the filter, in the order they're
+// listed. They're worked out again only once the view, the filter or the ordering is another
+// one, or the issues changed, which everything that changes them reports with `issues_changed`.
+#[derive(Debug, Default)]
+struct VisibleIssues {
+    key: Option<(String, String, ViewOrdering, usize)>,
+    rows: Vec<(usize, IssueMatch)>,
+}
+
+// where the filter matched an issue, as char indices into the fields shown in the list
+#[derive(Debug, Default, Clone)]
+struct IssueMatch {
+    score: i64,
+    identifier: Vec<usize>,
+    title: Vec<usize>,
+}
+
+// every word of the filter has to match one of the issue's fields
+fn match_issue(issue: &IssueFragment, filter: &str) -> Option<IssueMatch> {
+    let mut issue_match = IssueMatch::default();
+    for term in filter.split_whitespace() {
+        let shown = [
+            fuzzy_match(term, &issue.identifier).map(|(score, positions)| (score, positions, 0)),
+            fuzzy_match(term, &issue.title).map(|(score, positions)| (score, positions, 1)),
+        ];
+        let hidden = issue
+            .labels
+            .edges
+            .iter()
+            .map(|edge| edge.node.name.as_str())
+            .chain(issue.assignee.as_ref().map(|a| a.display_name.as_str()))
+            .chain(issue.project.as_ref().map(|p| p.name.as_str()))
+            .map(|field| fuzzy_match(term, field).map(|(score, positions)| (score, positions, 2)));
+        let (score, positions, field) = shown
+            .into_iter()
+            .chain(hidden)
+            .flatten()
+            .max_by_key(|(score, _, _)| *score)?;
+        issue_match.score += score;
+        match field {
+            0 => issue_match.identifier.extend(positions),
+            1 => issue_match.title.extend(positions),
+            _ => {}
+        }
+    }
+    Some(issue_match)
+}
+
+// splits `text` into spans, styling the chars at `positions`
+fn highlight(text: &str, positions: &[usize], style: Style) -> Vec<Span<'static>> {
+    let mut spans: Vec<Span> = vec![];
+    let mut run = String::new();
+    let mut run_highlighted = false;
+    for (index, c) in text.chars().enumerate() {
+        let highlighted = positions.contains(&index);
+        if highlighted != run_highlighted && !run.is_empty() {
+            let span = Span::from(std::mem::take(&mut run));
+            spans.push(if run_highlighted {
+                span.style(style)
+            } else {
+                span
+            });
+        }
+        run_highlighted = highlighted;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        let span = Span::from(run);
+        spans.push(if run_highlighted {
+            span.style(style)
+        } else {
+            span
+        });
+    }
+    spans
+}
+
+impl MyIssuesWidgetState {
+    fn ordering(&self) -> ViewOrdering {
+        self.orderings
+            .get(&self.selected_view_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    // the selected view's issues that match the filter: best match first, or in the view's order
+    fn visible_issues(&self) -> Vec<(&IssueFragment, IssueMatch)> {
+        let Some(issues) = self.issue_map.get(&self.selected_view_id) else {
+            return Vec::new();
+        };
+        let key = (
+            self.selected_view_id.clone(),
+            self.filter.clone(),
+            self.ordering(),
+            issues.len(),
+        );
+        let mut visible = self.visible.lock().unwrap();
+        if visible.key.as_ref() != Some(&key) {
+            visible.rows = self.match_issues(issues);
+            visible.key = Some(key);
+        }
+        visible
+            .rows
+            .iter()
+            .map(|(position, issue_match)| (&issues[*position], issue_match.clone()))
+            .collect()
+    }
+
+    fn match_issues(&self, issues: &[IssueFragment]) -> Vec<(usize, IssueMatch)> {
+        if self.filter.trim().is_empty() {
+            return self
+                .ordering()
+                .sort(issues)
+                .into_iter()
+                .map(|position| (position, IssueMatch::default()))
+                .collect();
+        }
+        let mut matches: Vec<(usize, IssueMatch)> = issues
+            .iter()
+            .enumerate()
+            .filter_map(|(position, issue)| Some((position, match_issue(issue, &self.filter)?)))
+            .collect();
+        matches.sort_by_key(|(_, issue_match)| std::cmp::Reverse(issue_match.score));
+        matches
+    }
+
+    // for anything that changes loaded issues, so the visible ones are worked out again
+    fn issues_changed(&mut self) {
+        *self.visible.get_mut().unwrap() = VisibleIssues::default();
+    }
+
+    fn selected(&self) -> Option<&IssueFragment> {
+        let index = self.list_state.selected()?;
+        self.visible_issues()
+            .into_iter()
+            .nth(index)
+            .map(|(issue, _)| issue)
+    }
+
+    // brings the state of blockers in every loaded view in line with fresher copies of them,
+    // so closing a blocker unblocks the issues it blocked
+    fn update_blockers(&mut self, issues: &[IssueFragment]) {
+        let state_types: HashMap<&str, &str> = issues
+            .iter()
+            .map(|issue| (issue.id.as_str(), issue.state.type_.as_str()))
+            .collect();
+        for issue in self.issue_map.values_mut().flatten() {
+            for blocker in &mut issue.blockers {
+                if let Some(state_type) = state_types.get(blocker.id.as_str()) {
+                    blocker.state_type = state_type.to_string();
+                }
+            }
+        }
+    }
+
+    fn change(&self, issue_id: &str) -> Option<IssueChange> {
+        self.changes
+            .get(issue_id)
+            .filter(|(_, until)| *until > Instant::now())
+            .map(|(change, _)| *change)
+    }
+
+    fn position(&self, issue_id: &str) -> Option<usize> {
+        self.visible_issues()
+            .iter()
+            .position(|(issue, _)| issue.id == issue_id)
+    }
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct MyIssuesWidget {
     pub state: Arc<RwLock<MyIssuesWidgetState>>,
+    client: Arc<LinearClient>,
+    keymap: Arc<Keymap>,
     input: Input,
     pub search_input_value: String,
     pub show_search_input: bool,
+    pub filtering: bool,
     pub input_mode: InputMode,
 }
 
 impl MyIssuesWidget {
-    async fn fetch_my_issues(self) {
-        self.set_loading_state(LoadingState::Loading);
-        self.set_selected_view(String::from("my_issues"));
-        let linear_api_token =
-            std::env::var("LINEAR_API_TOKEN").expect("Missing LINEAR_API_TOKEN env var");
-        let client = LinearClient::new(linear_api_token).unwrap();
-        let variables = my_issues_query::Variables {};
-        match client.query(MyIssuesQuery, variables).await {
-            Ok(data) => {
-                self.state.write().unwrap().issue_map.insert(
-                    String::from("my_issues"),
+    pub fn new(client: Arc<LinearClient>, keymap: Arc<Keymap>) -> Self {
+        Self {
+            client,
+            keymap,
+            ..Default::default()
+        }
+    }
+
+    async fn fetch_page(
+        &self,
+        view_id: &str,
+        after: Option<String>,
+    ) -> Result<(Vec<IssueFragment>, PageInfo), String> {
+        let client = &self.client;
+        match (view_id, view_filter(view_id, None)) {
+            ("my_issues", _) => {
+                let variables = my_issues_query::Variables {
+                    first: PAGE_SIZE,
+                    after,
+                };
+                let data = client
+                    .query(MyIssuesQuery, variables)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                Ok((
                     data.issues
                         .nodes
-                        .iter()
-                        .map(|issue| issue.to_owned().into())
+                        .into_iter()
+                        .map(|issue| issue.into())
                         .collect(),
-                );
-                self.state.write().unwrap().list_state.select(None);
+                    PageInfo {
+                        has_next_page: data.issues.page_info.has_next_page,
+                        end_cursor: data.issues.page_info.end_cursor,
+                        total_count: None,
+                        loading_more: false,
+                    },
+                ))
             }
-            Err(e) => {
-                self.set_loading_state(LoadingState::Error(e.to_string()));
-                return;
+            ("search_results", _) => {
+                let variables = search_query::Variables {
+                    term: self.state.read().unwrap().search_term.clone(),
+                    first: PAGE_SIZE,
+                    after,
+                };
+                let data = client
+                    .query(SearchQuery, variables)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                Ok((
+                    data.search_issues
+                        .nodes
+                        .into_iter()
+                        .map(|issue| issue.into())
+                        .collect(),
+                    PageInfo {
+                        has_next_page: data.search_issues.page_info.has_next_page,
+                        end_cursor: data.search_issues.page_info.end_cursor,
+                        total_count: Some(data.search_issues.total_count as usize),
+                        loading_more: false,
+                    },
+                ))
+            }
+            (_, Some(filter)) => {
+                let variables = filtered_issues_query::Variables {
+                    filter,
+                    first: PAGE_SIZE,
+                    after,
+                };
+                let data = client
+                    .query(FilteredIssuesQuery, variables)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                let issues = data.issues;
+                Ok((
+                    issues.nodes.into_iter().map(|issue| issue.into()).collect(),
+                    PageInfo {
+                        has_next_page: issues.page_info.has_next_page,
+                        end_cursor: issues.page_info.end_cursor,
+                        total_count: None,
+                        loading_more: false,
+                    },
+                ))
+            }
+            (custom_view_id, None) => {
+                let variables = custom_view_query::Variables {
+                    custom_view_id: custom_view_id.to_string(),
+                    first: PAGE_SIZE,
+                    after,
+                };
+                let data = client
+                    .query(CustomViewQuery, variables)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                let issues = data.custom_view.issues;
+                Ok((
+                    issues.nodes.into_iter().map(|issue| issue.into()).collect(),
+                    PageInfo {
+                        has_next_page: issues.page_info.has_next_page,
+                        end_cursor: issues.page_info.end_cursor,
+                        total_count: None,
+                        loading_more: false,
+                    },
+                ))
             }
         }
-        self.set_loading_state(LoadingState::Loaded);
     }
 
-    async fn fetch_custom_view(self, view: custom_views_query::ViewFragment) {
-        self.set_loading_state(LoadingState::Loading);
-        self.set_selected_view(view.id.clone());
-        let linear_api_token =
-            std::env::var("LINEAR_API_TOKEN").expect("Missing LINEAR_API_TOKEN env var");
-        let client = LinearClient::new(linear_api_token).unwrap();
-        let variables = custom_view_query::Variables {
-            custom_view_id: view.id.clone(),
-        };
-        match client.query(CustomViewQuery, variables).await {
-            Ok(data) => {
-                self.state.write().unwrap().issue_map.insert(
-                    view.id,
-                    data.custom_view
-                        .issues
+    // asks only for issues updated since `since`. Returns None when too much has changed
+    // to fit in one request, in which case a full refetch is cheaper.
+    async fn fetch_changes(
+        &self,
+        view_id: &str,
+        since: String,
+        ids: Vec<String>,
+    ) -> Result<Option<ViewUpdate>, String> {
+        let client = &self.client;
+        let filter = view_filter(view_id, Some(since.clone()));
+        let (updated, has_more): (Vec<IssueFragment>, bool) = match (view_id, filter) {
+            ("my_issues", _) => {
+                let variables = my_issues_updated_query::Variables {
+                    since: since.clone(),
+                };
+                let data = client
+                    .query(MyIssuesUpdatedQuery, variables)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                (
+                    data.issues
                         .nodes
-                        .iter()
-                        .map(|issue| issue.to_owned().into())
+                        .into_iter()
+                        .map(|issue| issue.into())
                         .collect(),
-                );
-                self.state.write().unwrap().list_state.select(None);
+                    data.issues.page_info.has_next_page,
+                )
             }
-            Err(e) => {
-                self.set_loading_state(LoadingState::Error(e.to_string()));
-                return;
+            (_, Some(filter)) => {
+                let variables = filtered_issues_query::Variables {
+                    filter,
+                    first: 250,
+                    after: None,
+                };
+                let data = client
+                    .query(FilteredIssuesQuery, variables)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                let issues = data.issues;
+                (
+                    issues.nodes.into_iter().map(|issue| issue.into()).collect(),
+                    issues.page_info.has_next_page,
+                )
             }
+            (custom_view_id, None) => {
+                let variables = custom_view_updated_query::Variables {
+                    custom_view_id: custom_view_id.to_string(),
+                    since: since.clone(),
+                };
+                let data = client
+                    .query(CustomViewUpdatedQuery, variables)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                let issues = data.custom_view.issues;
+                (
+                    issues.nodes.into_iter().map(|issue| issue.into()).collect(),
+                    issues.page_info.has_next_page,
+                )
+            }
+        };
+        if has_more {
+            return Ok(None);
         }
-        self.set_loading_state(LoadingState::Loaded);
+
+        // cached issues that changed but no longer match the view (completed, reassigned, ...)
+        let variables = issue_changes_query::Variables {
+            ids: Some(ids),
+            since,
+        };
+        let data = client
+            .query(IssueChangesQuery, variables)
+            .await
+            .map_err(|e| e.to_string())?;
+        if data.issues.page_info.has_next_page {
+            return Ok(None);
+        }
+        Ok(Some(ViewUpdate::Changes {
+            updated,
+            changed_ids: data
+                .issues
+                .nodes
+                .into_iter()
+                .map(|issue| issue.id)
+                .collect(),
+        }))
     }
 
-    async fn search_issues(self, search_term: String) {
-        self.set_loading_state(LoadingState::Loading);
-        self.set_selected_view(String::from("search_results"));
-        let linear_api_token =
-            std::env::var("LINEAR_API_TOKEN").expect("Missing LINEAR_API_TOKEN env var");
-        let client = LinearClient::new(linear_api_token).unwrap();
-        let variables = search_query::Variables {
-            term: search_term.to_string(),
+    async fn refresh_view(
+        &self,
+        view_id: &str,
+        sync: Option<(String, Vec<String>)>,
+    ) -> Result<ViewUpdate, String> {
+        if let Some((since, ids)) = sync
+            && let Some(update) = self.fetch_changes(view_id, since, ids).await?
+        {
+            return Ok(update);
+        }
+        let (issues, page_info) = self.fetch_page(view_id, None).await?;
+        Ok(ViewUpdate::Page(issues, page_info))
+    }
+
+    // loads the first page of a view. Views that were already loaded (or cached) stay
+    // usable while they refresh in the background, and only fetch what changed since
+    // their last sync.
+    async fn load_view(self, view_id: String) {
+        let started_at = chrono::Utc::now();
+        let (cached, sync) = {
+            let mut state = self.state.write().unwrap();
+            let cached = state.issue_map.contains_key(&view_id);
+            // search results depend on the term, so they are always fetched in full
+            let sync = match (state.synced_at.get(&view_id), state.issue_map.get(&view_id)) {
+                (Some(synced_at), Some(issues)) if view_id != "search_results" => {
+                    chrono::DateTime::parse_from_rfc3339(synced_at)
+                        .ok()
+                        .map(|synced_at| {
+                            let since = synced_at - chrono::Duration::seconds(SYNC_OVERLAP_SECONDS);
+                            (
+                                since.to_rfc3339(),
+                                issues.iter().map(|issue| issue.id.clone()).collect(),
+                            )
+                        })
+                }
+                _ => None,
+            };
+            if state.selected_view_id != view_id {
+                state.selected_view_id = view_id.clone();
+                state.list_state.select(None);
+            }
+            if cached {
+                state.refreshing.insert(view_id.clone());
+                state.loading_state = LoadingState::Loaded;
+            } else {
+                state.loading_state = LoadingState::Loading;
+            }
+            (cached, sync)
         };
-        match client.query(SearchQuery, variables).await {
-            Ok(data) => {
-                self.state.write().unwrap().issue_map.insert(
-                    "search_results".to_string(),
-                    data.search_issues
-                        .nodes
-                        .iter()
-                        .map(|issue| issue.to_owned().into())
-                        .collect(),
-                );
-                self.state.write().unwrap().list_state.select(None);
+
+        let result = self.refresh_view(&view_id, sync).await;
+
+        let mut state = self.state.write().unwrap();
+        state.refreshing.remove(&view_id);
+        let is_current = state.selected_view_id == view_id;
+        match result {
+            Ok(update) => {
+                // keep the same issue selected if it's still in the list
+                let selected_id = if is_current {
+                    state.selected().map(|issue| issue.id.clone())
+                } else {
+                    None
+                };
+                let previous: HashMap<String, String> = state
+                    .issue_map
+                    .get(&view_id)
+                    .iter()
+                    .flat_map(|issues| issues.iter())
+                    .map(|issue| (issue.id.clone(), issue.updated_at.clone()))
+                    .collect();
+                let issues = match update {
+                    ViewUpdate::Page(issues, page_info) => {
+                        state.page_info.insert(view_id.clone(), page_info);
+                        issues
+                    }
+                    ViewUpdate::Changes {
+                        updated,
+                        changed_ids,
+                    } => {
+                        let mut issues = state.issue_map.remove(&view_id).unwrap_or_default();
+                        merge_changes(&mut issues, updated, &changed_ids);
+                        issues
+                    }
+                };
+                // only a refresh of issues that were already shown has anything to mark
+                if cached {
+                    let now = Instant::now();
+                    state.changes.retain(|_, (_, until)| *until > now);
+                    for (id, change) in find_changes(&previous, &issues) {
+                        state
+                            .changes
+                            .insert(id, (change, now + CHANGE_MARK_DURATION));
+                    }
+                }
+                state.update_blockers(&issues);
+                state.issue_map.insert(view_id.clone(), issues);
+                state.issues_changed();
+                let selected = selected_id.and_then(|id| state.position(&id));
+                // anything updated while the request was in flight is picked up next time
+                state.synced_at.insert(view_id, started_at.to_rfc3339());
+                if is_current {
+                    state.list_state.select(selected);
+                    state.loading_state = LoadingState::Loaded;
+                }
+                drop(state);
+                self.save_cache();
             }
-            Err(e) => {
-                self.set_loading_state(LoadingState::Error(e.to_string()));
-                return;
+            Err(e) if is_current && cached => state.notice = Some(e),
+            Err(e) if is_current => state.loading_state = LoadingState::Error(e),
+            Err(_) => {}
+        }
+    }
+
+    pub fn load_cache(&self) {
+        let mut state = self.state.write().unwrap();
+        state.orderings = cache::load_orderings();
+        for (view_id, entry) in cache::load_issues() {
+            state.synced_at.insert(view_id.clone(), entry.fetched_at);
+            state.page_info.insert(
+                view_id.clone(),
+                PageInfo {
+                    has_next_page: entry.has_next_page,
+                    end_cursor: entry.end_cursor,
+                    total_count: None,
+                    loading_more: false,
+                },
+            );
+            state.issue_map.insert(view_id, entry.issues);
+        }
+        state.issues_changed();
+        if state.issue_map.contains_key("my_issues") {
+            state.selected_view_id = String::from("my_issues");
+            state.loading_state = LoadingState::Loaded;
+        }
+    }
+
+    fn save_cache(&self) {
+        let state = self.state.read().unwrap();
+        let entries: HashMap<String, CachedIssues> = state
+            .issue_map
+            .iter()
+            .filter(|(view_id, _)| is_cached(view_id))
+            .map(|(view_id, issues)| {
+                let mut entry = CachedIssues::new(issues.clone());
+                if let Some(synced_at) = state.synced_at.get(view_id) {
+                    entry.fetched_at = synced_at.clone();
+                }
+                if let Some(page_info) = state.page_info.get(view_id) {
+                    entry.has_next_page = page_info.has_next_page;
+                    entry.end_cursor = page_info.end_cursor.clone();
+                }
+                (view_id.clone(), entry)
+            })
+            .collect();
+        drop(state);
+        cache::save_issues(entries);
+    }
+
+    // re-sorts or regroups the selected view, keeping the same issue selected
+    fn change_ordering(&self, change: impl FnOnce(&mut ViewOrdering)) {
+        let mut state = self.state.write().unwrap();
+        let selected_id = state.selected().map(|issue| issue.id.clone());
+        let view_id = state.selected_view_id.clone();
+        change(state.orderings.entry(view_id).or_default());
+        let selected = selected_id.and_then(|id| state.position(&id));
+        state.list_state.select(selected);
+        let orderings = state.orderings.clone();
+        drop(state);
+        cache::save_orderings(orderings);
+    }
+
+    // reloads the selected view in the background, which only fetches what changed
+    pub fn refresh(&self) {
+        let state = self.state.read().unwrap();
+        if state.loading_state != LoadingState::Loaded
+            || state.refreshing.contains(&state.selected_view_id)
+        {
+            return;
+        }
+        let view_id = state.selected_view_id.clone();
+        drop(state);
+        tokio::spawn(self.clone().load_view(view_id));
+    }
+
+    async fn fetch_my_issues(self) {
+        self.load_view(String::from("my_issues")).await;
+    }
+
+    async fn fetch_custom_view(self, view: custom_views_query::ViewFragment) {
+        self.load_view(view.id).await;
+    }
+
+    async fn search_issues(self, search_term: String) {
+        {
+            let mut state = self.state.write().unwrap();
+            state.search_term = search_term;
+            // results for the previous term shouldn't show while the new ones load
+            state.issue_map.remove("search_results");
+            state.issues_changed();
+        }
+        self.load_view(String::from("search_results")).await;
+    }
+
+    // fetches the next page once the selection gets close to the end of the list
+    fn maybe_load_more(&self) {
+        let mut state = self.state.write().unwrap();
+        let view_id = state.selected_view_id.clone();
+        let (Some(selected), Some(issues), Some(page_info)) = (
+            state.list_state.selected(),
+            state.issue_map.get(&view_id),
+            state.page_info.get(&view_id),
+        ) else {
+            return;
+        };
+        // the filter only narrows what is already loaded
+        if page_info.loading_more
+            || !state.filter.is_empty()
+            || !page_info.has_next_page
+            || selected + PREFETCH_THRESHOLD < issues.len()
+        {
+            return;
+        }
+        let after = page_info.end_cursor.clone();
+        if let Some(page_info) = state.page_info.get_mut(&view_id) {
+            page_info.loading_more = true;
+        }
+        drop(state);
+        tokio::spawn(self.clone().load_more(view_id, after));
+    }
+
+    async fn load_more(self, view_id: String, after: Option<String>) {
+        let result = self.fetch_page(&view_id, after).await;
+        let mut state = self.state.write().unwrap();
+        if let Some(page_info) = state.page_info.get_mut(&view_id) {
+            page_info.loading_more = false;
+        }
+        match result {
+            Ok((issues, page_info)) => {
+                state.update_blockers(&issues);
+                let loaded = state.issue_map.entry(view_id.clone()).or_default();
+                // a refresh since the last page may already have brought some of these in
+                let issues: Vec<IssueFragment> = issues
+                    .into_iter()
+                    .filter(|issue| !loaded.iter().any(|existing| existing.id == issue.id))
+                    .collect();
+                loaded.extend(issues);
+                state.issues_changed();
+                state.page_info.insert(view_id, page_info);
+                drop(state);
+                self.save_cache();
             }
+            Err(e) => state.notice = Some(format!("Couldn't load more issues: {}", e)),
         }
-        self.set_loading_state(LoadingState::Loaded);
     }
 
     pub fn toggle_search_mode(&mut self) {
         if self.show_search_input {
             self.show_search_input = false;
             self.input_mode = InputMode::Normal;
+            if self.filtering {
+                self.filtering = false;
+                self.set_filter("");
+            }
         } else {
             self.show_search_input = true;
             self.input.reset();
@@ -139,12 +848,32 @@ impl MyIssuesWidget {
         }
     }
 
-    fn set_selected_view(&self, id: String) {
-        self.state.write().unwrap().selected_view_id = id;
+    // the filter shares the search input, but narrows the loaded list as you type
+    pub fn toggle_filter_mode(&mut self) {
+        let opening = !self.show_search_input;
+        self.toggle_search_mode();
+        self.filtering = opening;
     }
 
-    fn set_loading_state(&self, state: LoadingState) {
-        self.state.write().unwrap().loading_state = state;
+    fn set_filter(&self, filter: &str) {
+        let mut state = self.state.write().unwrap();
+        let selected_id = state.selected().map(|issue| issue.id.clone());
+        state.filter = filter.to_string();
+        // best match first while typing, the same issue again once the filter is cleared
+        let selected = match filter.is_empty() {
+            true => selected_id.and_then(|id| state.position(&id)),
+            false => None,
+        };
+        let selected = selected.or(if state.visible_issues().is_empty() {
+            None
+        } else {
+            Some(0)
+        });
+        state.list_state.select(selected);
+    }
+
+    fn set_selected_view(&self, id: String) {
+        self.state.write().unwrap().selected_view_id = id;
     }
 
     fn get_loading_state(&self) -> LoadingState {
@@ -154,53 +883,332 @@ impl MyIssuesWidget {
     pub fn scroll_down(&self) {
         let mut state = self.state.write().unwrap();
 
-        match (
-            state.list_state.selected(),
-            state.issue_map.get(&state.selected_view_id),
-        ) {
-            (Some(index), Some(map)) if index >= map.len() - 1 => state.list_state.select_first(),
-            (_, Some(map)) if !map.is_empty() => state.list_state.select_next(),
+        let len = state.visible_issues().len();
+        match state.list_state.selected() {
+            Some(index) if len > 0 && index >= len - 1 => state.list_state.select_first(),
+            _ if len > 0 => state.list_state.select_next(),
             _ => (),
         }
+        drop(state);
+        self.maybe_load_more();
     }
 
     pub fn scroll_up(&self) {
         let mut state = self.state.write().unwrap();
 
-        match (
-            state.list_state.selected(),
-            state.issue_map.get(&state.selected_view_id),
-        ) {
-            (Some(0) | None, Some(map)) if !map.is_empty() => {
-                let max_index = map.len() - 1;
-                state.list_state.select(Some(max_index));
-            }
-            (_, Some(map)) if !map.is_empty() => state.list_state.select_previous(),
+        let len = state.visible_issues().len();
+        match state.list_state.selected() {
+            Some(0) | None if len > 0 => state.list_state.select(Some(len - 1)),
+            _ if len > 0 => state.list_state.select_previous(),
             _ => (),
         }
     }
 
-    pub fn copy_branch_name(&self) {
+    pub fn selected_issue(&self) -> Option<IssueFragment> {
+        self.state.read().unwrap().selected().cloned()
+    }
+
+    // the loaded issues of the selected view that match the filter
+    pub fn current_issues(&self) -> Vec<IssueFragment> {
         let state = self.state.read().unwrap();
-        if let (Some(index), Some(map)) = (
-            state.list_state.selected(),
-            state.issue_map.get(&state.selected_view_id),
-        ) {
-            let branch_name = map[index].branch_name.clone();
-            cli_clipboard::set_contents(branch_name).unwrap();
+        state
+            .visible_issues()
+            .into_iter()
+            .map(|(issue, _)| issue.clone())
+            .collect()
+    }
+
+    pub fn select_issue(&self, issue_id: &str) {
+        let mut state = self.state.write().unwrap();
+        let index = state.position(issue_id);
+        if index.is_some() {
+            state.list_state.select(index);
         }
     }
 
-    pub fn open_url(&self) -> std::result::Result<(), std::io::Error> {
-        let state = self.state.read().unwrap();
-        if let (Some(index), Some(map)) = (
-            state.list_state.selected(),
-            state.issue_map.get(&state.selected_view_id),
-        ) {
-            let url = map[index].url.clone();
-            open::that(&url)
-        } else {
-            Ok(())
+    fn find_issue(&self, issue_id: &str) -> Option<IssueFragment> {
+        self.state
+            .read()
+            .unwrap()
+            .issue_map
+            .values()
+            .flatten()
+            .find(|issue| issue.id == issue_id)
+            .cloned()
+    }
+
+    // replaces every copy of the issue across the loaded views
+    pub fn apply_issue(&self, issue: &IssueFragment) {
+        let mut state = self.state.write().unwrap();
+        for existing in state.issue_map.values_mut().flatten() {
+            if existing.id == issue.id {
+                *existing = issue.clone();
+            }
+        }
+        state.update_blockers(std::slice::from_ref(issue));
+        state.issues_changed();
+    }
+
+    fn set_notice(&self, notice: Option<String>) {
+        self.state.write().unwrap().notice = notice;
+    }
+
+    // optimistically moves the issue to `workflow_state`, rolling back if Linear rejects it
+    pub fn update_issue_state(
+        &self,
+        issue_id: &str,
+        workflow_state: WorkflowStateFragment,
+        selected_issue_widget: SelectedIssueWidget,
+    ) {
+        let Some(previous) = self.find_issue(issue_id) else {
+            return;
+        };
+        let mut updated = previous.clone();
+        updated.state = IssueFragmentState {
+            id: workflow_state.id.clone(),
+            name: workflow_state.name,
+            color: workflow_state.color,
+            type_: workflow_state.type_,
+        };
+        self.apply_issue(&updated);
+        selected_issue_widget.refresh_issue(&updated);
+        self.set_notice(None);
+
+        let input = issue_update_mutation::IssueUpdateInput {
+            state_id: Some(workflow_state.id),
+            ..Default::default()
+        };
+        tokio::spawn(
+            self.clone()
+                .update_issue(previous, input, selected_issue_widget),
+        );
+    }
+
+    async fn update_issue(
+        self,
+        previous: IssueFragment,
+        input: issue_update_mutation::IssueUpdateInput,
+        selected_issue_widget: SelectedIssueWidget,
+    ) {
+        let client = &self.client;
+        let variables = issue_update_mutation::Variables {
+            id: previous.id.clone(),
+            input,
+        };
+        match client.query(IssueUpdateMutation, variables).await {
+            Ok(data) => {
+                if let Some(issue) = data.issue_update.issue {
+                    let issue: IssueFragment = issue.into();
+                    self.apply_issue(&issue);
+                    selected_issue_widget.refresh_issue(&issue);
+                }
+            }
+            Err(e) => {
+                self.apply_issue(&previous);
+                selected_issue_widget.refresh_issue(&previous);
+                self.set_notice(Some(format!("{}: {}", previous.identifier, e)));
+            }
+        }
+    }
+
+    // saves a description edited from `issue`. Unless `overwrite` is set, an issue that was
+    // changed in Linear in the meantime is held back for the user to confirm.
+    pub fn update_description(
+        &self,
+        issue: IssueFragment,
+        description: String,
+        overwrite: bool,
+        selected_issue_widget: SelectedIssueWidget,
+    ) {
+        self.set_notice(None);
+        tokio::spawn(self.clone().save_description(
+            issue,
+            description,
+            overwrite,
+            selected_issue_widget,
+        ));
+    }
+
+    async fn save_description(
+        self,
+        issue: IssueFragment,
+        description: String,
+        overwrite: bool,
+        selected_issue_widget: SelectedIssueWidget,
+    ) {
+        let mut previous = issue;
+        if !overwrite {
+            let variables = issue_query::Variables {
+                id: previous.id.clone(),
+            };
+            match self.client.query(IssueQuery, variables).await {
+                Ok(data) if data.issue.updated_at != previous.updated_at => {
+                    selected_issue_widget.hold_description(PendingDescription {
+                        issue: data.issue.into(),
+                        description,
+                    });
+                    return;
+                }
+                Ok(data) => previous = data.issue.into(),
+                Err(e) => {
+                    self.set_notice(Some(format!("{}: {}", previous.identifier, e)));
+                    return;
+                }
+            }
+        }
+        let mut updated = previous.clone();
+        updated.description = Some(description.clone());
+        self.apply_issue(&updated);
+        selected_issue_widget.refresh_issue(&updated);
+
+        let input = issue_update_mutation::IssueUpdateInput {
+            description: Some(description),
+            ..Default::default()
+        };
+        self.update_issue(previous, input, selected_issue_widget)
+            .await;
+    }
+
+    pub fn create_issue(
+        &self,
+        input: issue_create_mutation::IssueCreateInput,
+        selected_issue_widget: SelectedIssueWidget,
+        issue_form_widget: IssueFormWidget,
+    ) {
+        self.set_notice(None);
+        let view_id = self.state.read().unwrap().selected_view_id.clone();
+        tokio::spawn(self.clone().create_issue_in_view(
+            view_id,
+            input,
+            selected_issue_widget,
+            issue_form_widget,
+        ));
+    }
+
+    // adds the new issue to the top of the view it was created from and selects it, or gives the
+    // form back with what was typed
+    async fn create_issue_in_view(
+        self,
+        view_id: String,
+        input: issue_create_mutation::IssueCreateInput,
+        mut selected_issue_widget: SelectedIssueWidget,
+        issue_form_widget: IssueFormWidget,
+    ) {
+        let client = &self.client;
+        let variables = issue_create_mutation::Variables {
+            input: input.clone(),
+        };
+        let error = match client.query(IssueCreateMutation, variables).await {
+            Ok(data) => match data.issue_create.issue {
+                Some(issue) => {
+                    let issue: IssueFragment = issue.into();
+                    self.insert_issue(&view_id, issue.clone());
+                    selected_issue_widget.set_selected_issue(Some(issue));
+                    return;
+                }
+                None => String::from("Linear didn't return the new issue"),
+            },
+            Err(e) => e.to_string(),
+        };
+        issue_form_widget.restore(&input, format!("Couldn't create issue: {}", error));
+    }
+
+    pub fn create_relation(
+        &self,
+        relation: NewRelation,
+        selected_issue_widget: SelectedIssueWidget,
+    ) {
+        self.set_notice(None);
+        tokio::spawn(self.clone().add_relation(relation, selected_issue_widget));
+    }
+
+    async fn add_relation(self, relation: NewRelation, selected_issue_widget: SelectedIssueWidget) {
+        let client = &self.client;
+        // the picker hands over a typed identifier as is, so the issue is looked up first
+        let variables = issue_query::Variables {
+            id: relation.target.clone(),
+        };
+        let target_id = match client.query(IssueQuery, variables).await {
+            Ok(data) => data.issue.id,
+            Err(e) => {
+                self.set_notice(Some(format!("{}: {}", relation.target, e)));
+                return;
+            }
+        };
+        let variables = issue_relation_create_mutation::Variables {
+            input: relation.input(target_id.clone()),
+        };
+        match client.query(IssueRelationCreateMutation, variables).await {
+            Ok(_) => {
+                self.reload_issues(&[relation.issue_id, target_id], &selected_issue_widget)
+                    .await;
+                selected_issue_widget.load_relations();
+            }
+            Err(e) => self.set_notice(Some(format!("Couldn't add relation: {}", e))),
+        }
+    }
+
+    pub fn delete_relation(
+        &self,
+        issue_id: String,
+        relation: Relation,
+        selected_issue_widget: SelectedIssueWidget,
+    ) {
+        self.set_notice(None);
+        tokio::spawn(
+            self.clone()
+                .remove_relation(issue_id, relation, selected_issue_widget),
+        );
+    }
+
+    async fn remove_relation(
+        self,
+        issue_id: String,
+        relation: Relation,
+        selected_issue_widget: SelectedIssueWidget,
+    ) {
+        let client = &self.client;
+        let variables = issue_relation_delete_mutation::Variables { id: relation.id };
+        match client.query(IssueRelationDeleteMutation, variables).await {
+            Ok(_) => {
+                self.reload_issues(&[issue_id, relation.issue.id], &selected_issue_widget)
+                    .await;
+                selected_issue_widget.load_relations();
+            }
+            Err(e) => self.set_notice(Some(format!("Couldn't remove relation: {}", e))),
+        }
+    }
+
+    // fetches fresh copies of issues whose blocked marker may have changed
+    async fn reload_issues(
+        &self,
+        issue_ids: &[String],
+        selected_issue_widget: &SelectedIssueWidget,
+    ) {
+        for issue_id in issue_ids {
+            let variables = issue_query::Variables {
+                id: issue_id.clone(),
+            };
+            if let Ok(data) = self.client.query(IssueQuery, variables).await {
+                let issue: IssueFragment = data.issue.into();
+                self.apply_issue(&issue);
+                selected_issue_widget.refresh_issue(&issue);
+            }
+        }
+    }
+
+    pub fn insert_issue(&self, view_id: &str, issue: IssueFragment) {
+        let id = issue.id.clone();
+        let mut state = self.state.write().unwrap();
+        state
+            .issue_map
+            .entry(view_id.to_string())
+            .or_default()
+            .insert(0, issue);
+        state.issues_changed();
+        if state.selected_view_id == view_id {
+            let index = state.position(&id);
+            state.list_state.select(index);
         }
     }
 
@@ -213,6 +1221,12 @@ impl MyIssuesWidget {
             TabChangeEvent::FetchCustomViewIssues(view) => {
                 tokio::spawn(this.fetch_custom_view(view));
             }
+            TabChangeEvent::FetchProjectIssues(project_id) => {
+                tokio::spawn(this.load_view(format!("{}{}", PROJECT_VIEW_PREFIX, project_id)));
+            }
+            TabChangeEvent::FetchCycleIssues(cycle_id) => {
+                tokio::spawn(this.load_view(format!("{}{}", CYCLE_VIEW_PREFIX, cycle_id)));
+            }
             TabChangeEvent::SearchIssues => {
                 self.set_selected_view(String::from("search_results"));
             }
@@ -220,48 +1234,66 @@ impl MyIssuesWidget {
         }
     }
 
-    pub fn handle_event(&mut self, event: &Event) -> LtEvent {
+    pub fn handle_event(&mut self, event: &Event) -> LtEvent<'_> {
         if self.get_loading_state() != LoadingState::Loaded {
             return LtEvent::None;
         }
-        if let Event::Key(key) = event {
-            if key.kind == KeyEventKind::Press {
-                use InputMode::{Editing, Normal};
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            use InputMode::{Editing, Normal};
 
-                match (self.input_mode.clone(), key.code) {
-                    (Normal, KeyCode::Char('j')) => {
-                        self.scroll_down();
-                        return LtEvent::SelectIssue;
-                    }
-                    (Normal, KeyCode::Char('k')) => {
-                        self.scroll_up();
-                        return LtEvent::SelectIssue;
-                    }
-                    (Normal, KeyCode::Char('o')) => {
-                        let _ = self.open_url();
-                        return LtEvent::None;
-                    }
-                    (Normal, KeyCode::Char('c') | KeyCode::Char('y')) => {
-                        self.copy_branch_name();
-                        return LtEvent::None;
-                    }
-                    (Editing, KeyCode::Enter) => {
-                        self.input_mode = InputMode::Normal;
-                        // tab?
-                        tokio::spawn(self.clone().search_issues(String::from(self.input.value())));
-                        return LtEvent::SearchIssues(self.input.value());
-                    }
-                    (Editing, _) => {
-                        if self.show_search_input {
-                            self.input.handle_event(event);
-                        }
-                        return LtEvent::None;
+            match (self.input_mode.clone(), self.keymap.action(key)) {
+                (Normal, Some(Action::Down)) => {
+                    self.scroll_down();
+                    return LtEvent::SelectIssue;
+                }
+                (Normal, Some(Action::Up)) => {
+                    self.scroll_up();
+                    return LtEvent::SelectIssue;
+                }
+                (Normal, Some(Action::Sort)) => {
+                    self.change_ordering(ViewOrdering::next_sort);
+                    return LtEvent::None;
+                }
+                (Normal, Some(Action::Group)) => {
+                    self.change_ordering(ViewOrdering::next_group);
+                    return LtEvent::None;
+                }
+                (Normal, Some(Action::NewIssue)) => {
+                    return LtEvent::NewIssue;
+                }
+                (Normal, Some(Action::ChangeState)) => {
+                    return match self.selected_issue() {
+                        Some(_) => LtEvent::ChangeIssueState,
+                        None => LtEvent::None,
+                    };
+                }
+                (Editing, _) if key.code == KeyCode::Enter && self.filtering => {
+                    self.input_mode = InputMode::Normal;
+                    return LtEvent::SelectIssue;
+                }
+                (Editing, _) if self.filtering => {
+                    self.input.handle_event(event);
+                    self.set_filter(self.input.value());
+                    return LtEvent::SelectIssue;
+                }
+                (Editing, _) if key.code == KeyCode::Enter => {
+                    self.input_mode = InputMode::Normal;
+                    // tab?
+                    tokio::spawn(self.clone().search_issues(String::from(self.input.value())));
+                    return LtEvent::SearchIssues(self.input.value());
+                }
+                (Editing, _) => {
+                    if self.show_search_input {
+                        self.input.handle_event(event);
                     }
-                    _ => {
-                        return LtEvent::None;
-                    }
-                };
-            }
+                    return LtEvent::None;
+                }
+                _ => {
+                    return LtEvent::None;
+                }
+            };
         }
         LtEvent::None
     }
@@ -271,6 +1303,39 @@ const SELECTED_STYLE: Style = Style::new()
     .add_modifier(Modifier::BOLD)
     .add_modifier(Modifier::ITALIC);
 
+fn issue_item(
+    item: &IssueFragment,
+    issue_match: &IssueMatch,
+    change: Option<IssueChange>,
+    area_width: u16,
+) -> ListItem<'static> {
+    let mut text = Text::default();
+    let priority_icon = iconmap::p_to_nf(item.priority);
+    let status_icon = iconmap::state_to_nf(&item.state.type_);
+    let identifier = item.identifier.clone();
+    let blocked = if item.blocked() { " blocked" } else { "" };
+    let change = change.map_or("", |change| change.label());
+    // gives the effect of right aligning icons and left aligning the text
+    let spaces =
+        (area_width as usize).saturating_sub(identifier.len() + change.len() + blocked.len() + 8);
+    let mut line = Line::from(highlight(&identifier, &issue_match.identifier, MATCH_STYLE));
+    line.push_span(Span::from(change).green());
+    line.push_span(Span::from(blocked).red());
+    line.push_span(format!(
+        "{}{}  {}",
+        " ".repeat(spaces),
+        status_icon,
+        priority_icon
+    ));
+    text.extend([
+        Line::from(highlight(&item.title, &issue_match.title, MATCH_STYLE)),
+        line,
+    ]);
+    ListItem::new(text)
+}
+
+const MATCH_STYLE: Style = Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+
 impl Widget for &MyIssuesWidget {
     fn render(self, area: Rect, buf: &mut Buffer) {
         use Constraint::{Length, Min};
@@ -287,16 +1352,59 @@ impl Widget for &MyIssuesWidget {
         };
 
         let mut block = Block::bordered().title_bottom(Line::from(vec![
-            Span::from(" <j/k> ").blue(),
+            Span::from(format!(
+                " <{}/{}> ",
+                self.keymap.label(Action::Down),
+                self.keymap.label(Action::Up)
+            ))
+            .blue(),
             Span::from("to select "),
             Span::from("─"),
-            Span::from(" <⁄> ").blue(),
+            Span::from(format!(" <{}> ", self.keymap.label(Action::Search))).blue(),
             Span::from("to search"),
         ]));
 
         if let LoadingState::Loading = self.get_loading_state() {
             block = block.title(Line::from("Loading…").right_aligned());
             loading = true;
+        } else {
+            let state = self.state.read().unwrap();
+            if let Some(page_info) = state.page_info.get(&state.selected_view_id) {
+                let loaded = state
+                    .issue_map
+                    .get(&state.selected_view_id)
+                    .map_or(0, |issues| issues.len());
+                let mut title = if !state.filter.is_empty() {
+                    format!("{} of {} match", state.visible_issues().len(), loaded)
+                } else if page_info.loading_more {
+                    format!("{} · loading more…", loaded)
+                } else {
+                    page_info.describe(loaded)
+                };
+                let ordering = state.ordering();
+                if !ordering.is_default() {
+                    title.push_str(&format!(" · by {}", ordering.sort.label()));
+                    if ordering.group != GroupKey::None {
+                        title.push_str(&format!(", grouped by {}", ordering.group.label()));
+                    }
+                }
+                block = block.title(Line::from(format!(" {} ", title)).dark_gray());
+            }
+            let synced_at = state
+                .synced_at
+                .get(&state.selected_view_id)
+                .and_then(|synced_at| chrono::DateTime::parse_from_rfc3339(synced_at).ok());
+            if let Some(synced_at) = synced_at {
+                let synced_at = synced_at.with_timezone(&chrono::Local).format("%H:%M");
+                block = block.title_bottom(
+                    Line::from(format!(" synced {} ", synced_at).dark_gray()).right_aligned(),
+                );
+            }
+            if let Some(notice) = &state.notice {
+                block = block.title(Line::from(notice.clone().red()).right_aligned());
+            } else if state.refreshing.contains(&state.selected_view_id) {
+                block = block.title(Line::from("Refreshing…".dark_gray()).right_aligned());
+            }
         }
 
         if let LoadingState::Error(e) = self.get_loading_state() {
@@ -311,32 +1419,41 @@ impl Widget for &MyIssuesWidget {
         }
         let mut state = self.state.write().unwrap();
         let area_width = area.width;
-        let rows: Vec<ListItem> = match state.issue_map.get(&state.selected_view_id) {
-            Some(issues) => issues
-                .iter()
-                .map(|item| {
-                    let mut text = Text::default();
-                    let priority_icon = iconmap::p_to_nf(item.priority);
-                    let status_icon = iconmap::state_to_nf(&item.state.type_);
-                    let identifier = item.identifier.clone();
-                    // gives the effect of right aligning icons and left aligning the text
-                    let spaces = (area_width as usize) - identifier.len() - 8;
-                    let line = format!(
-                        "{}{}{}  {}",
-                        identifier.fg(AMBER.c700),
-                        " ".repeat(spaces),
-                        status_icon,
-                        priority_icon
-                    );
-                    text.extend([
-                        item.title.clone(),
-                        line.add_modifier(Modifier::BOLD).blue().to_string(),
-                    ]);
-                    ListItem::new(text)
-                })
-                .collect(),
-            None => vec![],
-        };
+        let ordering = state.ordering();
+        // filtered lists are ranked by match, so they aren't grouped
+        let grouped = state.filter.is_empty() && ordering.group != GroupKey::None;
+        let selected = state.list_state.selected();
+        let mut rows: Vec<ListItem> = vec![];
+        let mut selected_row = None;
+        {
+            let visible = state.visible_issues();
+            let mut group_sizes: HashMap<String, usize> = HashMap::new();
+            if grouped {
+                for (issue, _) in &visible {
+                    if let Some(label) = ordering.group_label(issue) {
+                        *group_sizes.entry(label).or_default() += 1;
+                    }
+                }
+            }
+            let mut current_group = None;
+            for (index, (item, issue_match)) in visible.into_iter().enumerate() {
+                let label = ordering.group_label(item).filter(|_| grouped);
+                if let Some(label) = &label
+                    && current_group.as_ref() != Some(label)
+                {
+                    rows.push(ListItem::new(Line::from(vec![
+                        Span::from(label.clone()).bold().magenta(),
+                        Span::from(format!(" {}", group_sizes[label])).dark_gray(),
+                    ])));
+                    current_group = Some(label.clone());
+                }
+                if selected == Some(index) {
+                    selected_row = Some(rows.len());
+                }
+                let change = state.change(&item.id);
+                rows.push(issue_item(item, &issue_match, change, area_width));
+            }
+        }
 
         // tests can't see the highlighting
         let highlight_symbol = if cfg!(test) { ">" } else { "" };
@@ -346,7 +1463,12 @@ impl Widget for &MyIssuesWidget {
                 .highlight_style(SELECTED_STYLE)
                 .highlight_symbol(highlight_symbol)
                 .block(block);
-            StatefulWidget::render(list, body_area, buf, &mut state.list_state);
+            if grouped {
+                state.row_state.select(selected_row);
+                StatefulWidget::render(list, body_area, buf, &mut state.row_state);
+            } else {
+                StatefulWidget::render(list, body_area, buf, &mut state.list_state);
+            }
         } else {
             Paragraph::new("No issues found".red())
                 .block(block)
@@ -360,6 +1482,11 @@ impl Widget for &MyIssuesWidget {
             } else {
                 self.input.value().to_string()
             };
+            let block2 = if self.filtering {
+                block2.title(" Filter ")
+            } else {
+                block2
+            };
             let input = Paragraph::new(value).style(Color::Yellow).block(block2);
             input.render(search_area, buf);
         }
@@ -369,28 +1496,27 @@ impl Widget for &MyIssuesWidget {
 #[cfg(test)]
 mod tests {
     use std::{
-        collections::HashMap,
+        collections::{HashMap, HashSet},
         sync::{Arc, RwLock},
     };
 
-    use crossterm::event::{KeyCode, KeyEventKind, KeyEventState, KeyModifiers};
+    use crossterm::event::KeyCode;
     use insta::assert_snapshot;
     use ratatui::{Terminal, backend::TestBackend, widgets::ListState};
+    use serde_json::json;
     use tui_input::Input;
 
     use crate::{
-        InputMode, LtEvent,
-        widgets::{self, MyIssuesWidget, selected_issue::tests::make_issue},
+        InputMode, IssueBlocker, IssueFragment, LoadingState, LtEvent,
+        queries::{custom_views_query, issue_create_mutation},
+        stub_server::StubServer,
+        widgets::{
+            self, MyIssuesWidget, NewRelation, Relation, RelationKind, create_key_event,
+            selected_issue::tests::make_issue,
+        },
     };
 
-    fn create_key_event(key: char) -> crossterm::event::Event {
-        crossterm::event::Event::Key(crossterm::event::KeyEvent {
-            code: KeyCode::Char(key),
-            kind: KeyEventKind::Press,
-            modifiers: KeyModifiers::empty(),
-            state: KeyEventState::empty(),
-        })
-    }
+    use super::{IssueChange, find_changes, is_cached, merge_changes, view_filter};
 
     #[test]
     fn test_empty_state() {
@@ -402,7 +1528,7 @@ mod tests {
 
         assert_snapshot!(terminal.backend());
 
-        let ev = app.handle_event(&create_key_event('j'));
+        let ev = app.handle_event(&create_key_event(KeyCode::Char('j')));
         assert_eq!(ev, LtEvent::None);
     }
 
@@ -414,6 +1540,7 @@ mod tests {
         ];
         let mut app = MyIssuesWidget {
             show_search_input: false,
+            filtering: false,
             input: Input::default(),
             input_mode: InputMode::Normal,
             search_input_value: String::from(""),
@@ -422,7 +1549,19 @@ mod tests {
                 selected_view_id: String::from("my_issues"),
                 list_state: ListState::default(),
                 issue_map: HashMap::from([(String::from("my_issues"), issues)]),
+                page_info: HashMap::new(),
+                refreshing: HashSet::new(),
+                synced_at: HashMap::new(),
+                changes: HashMap::new(),
+                search_term: String::new(),
+                notice: None,
+                filter: String::new(),
+                orderings: HashMap::new(),
+                row_state: ListState::default(),
+                visible: Default::default(),
             })),
+            client: Arc::default(),
+            keymap: Arc::default(),
         };
         let mut terminal = Terminal::new(TestBackend::new(60, 20)).unwrap();
         terminal
@@ -433,11 +1572,11 @@ mod tests {
 
         assert_eq!(app.state.read().unwrap().list_state.selected(), None);
 
-        let ev = app.handle_event(&create_key_event('j'));
+        let ev = app.handle_event(&create_key_event(KeyCode::Char('j')));
         assert_eq!(ev, LtEvent::SelectIssue);
         assert_eq!(app.state.read().unwrap().list_state.selected(), Some(0));
 
-        app.handle_event(&create_key_event('j'));
+        app.handle_event(&create_key_event(KeyCode::Char('j')));
         assert_eq!(app.state.read().unwrap().list_state.selected(), Some(1));
 
         terminal
@@ -446,18 +1585,13 @@ mod tests {
 
         assert_snapshot!(terminal.backend());
         // test that is passes through back to 0
-        app.handle_event(&create_key_event('j'));
+        app.handle_event(&create_key_event(KeyCode::Char('j')));
         assert_eq!(app.state.read().unwrap().list_state.selected(), Some(0));
 
         // test that is passes backwards to 1
-        app.handle_event(&create_key_event('k'));
+        app.handle_event(&create_key_event(KeyCode::Char('k')));
         assert_eq!(app.state.read().unwrap().list_state.selected(), Some(1));
 
-        // test that <y> yanks the branch name to the clipboard
-        // disabling because CI can't run this
-        //app.handle_event(&create_key_event('y'));
-        //assert_eq!(cli_clipboard::get_contents().unwrap(), "test-1-branch-name");
-
         assert!(!app.show_search_input);
         assert_eq!(app.input_mode, InputMode::Normal);
         app.toggle_search_mode();
@@ -471,4 +1605,561 @@ mod tests {
         assert!(!app.show_search_input);
         assert_eq!(app.input_mode, InputMode::Normal);
     }
+
+    #[test]
+    fn test_page_info() {
+        let app = MyIssuesWidget::default();
+        {
+            let mut state = app.state.write().unwrap();
+            state.loading_state = crate::LoadingState::Loaded;
+            state.selected_view_id = String::from("my_issues");
+            state.issue_map.insert(
+                String::from("my_issues"),
+                vec![make_issue("Ticket One", "TEST-1")],
+            );
+            state.page_info.insert(
+                String::from("my_issues"),
+                widgets::issue_list::PageInfo {
+                    has_next_page: true,
+                    end_cursor: Some(String::from("cursor")),
+                    total_count: None,
+                    loading_more: false,
+                },
+            );
+        }
+        let mut terminal = Terminal::new(TestBackend::new(60, 8)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        let page_info = widgets::issue_list::PageInfo {
+            has_next_page: false,
+            end_cursor: None,
+            total_count: Some(12),
+            loading_more: false,
+        };
+        assert_eq!(page_info.describe(12), "12 of 12");
+    }
+
+    #[test]
+    fn test_filter() {
+        let mut app = MyIssuesWidget::default();
+        {
+            let mut state = app.state.write().unwrap();
+            state.loading_state = crate::LoadingState::Loaded;
+            state.selected_view_id = String::from("my_issues");
+            let mut labelled = make_issue("Crash on startup", "TEST-3");
+            labelled.labels = serde_json::from_str(
+                r##"{"edges": [{"node": {"name": "Regression", "color": "#FF0000"}}]}"##,
+            )
+            .unwrap();
+            state.issue_map.insert(
+                String::from("my_issues"),
+                vec![
+                    make_issue("Login page is slow", "TEST-1"),
+                    make_issue("Fix logout", "TEST-2"),
+                    labelled,
+                ],
+            );
+        }
+
+        app.toggle_filter_mode();
+        assert!(app.filtering);
+        for c in "log".chars() {
+            assert_eq!(
+                app.handle_event(&create_key_event(KeyCode::Char(c))),
+                LtEvent::SelectIssue
+            );
+        }
+        let identifiers: Vec<String> = app
+            .current_issues()
+            .into_iter()
+            .map(|issue| issue.identifier)
+            .collect();
+        assert_eq!(identifiers, ["TEST-1", "TEST-2"]);
+        assert_eq!(app.selected_issue().unwrap().identifier, "TEST-1");
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 10)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        // labels match too, even though they aren't shown in the list
+        app.toggle_filter_mode();
+        app.toggle_filter_mode();
+        for c in "regr".chars() {
+            app.handle_event(&create_key_event(KeyCode::Char(c)));
+        }
+        assert_eq!(app.selected_issue().unwrap().identifier, "TEST-3");
+
+        // clearing the filter keeps the same issue selected
+        app.toggle_search_mode();
+        assert!(!app.filtering);
+        assert_eq!(app.current_issues().len(), 3);
+        assert_eq!(app.selected_issue().unwrap().identifier, "TEST-3");
+
+        // an issue that changes is matched again
+        app.toggle_filter_mode();
+        for c in "regr".chars() {
+            app.handle_event(&create_key_event(KeyCode::Char(c)));
+        }
+        assert_eq!(app.current_issues().len(), 1);
+        app.apply_issue(&make_issue("Regression in logout", "TEST-2"));
+        assert_eq!(app.current_issues().len(), 2);
+    }
+
+    #[test]
+    fn test_sort_and_group() {
+        let mut app = MyIssuesWidget::default();
+        {
+            let mut state = app.state.write().unwrap();
+            state.loading_state = crate::LoadingState::Loaded;
+            state.selected_view_id = String::from("my_issues");
+            let mut unplanned = make_issue("Unplanned", "TEST-1");
+            unplanned.project = None;
+            let mut newest = make_issue("Newest", "TEST-2");
+            newest.created_at = String::from("2025-06-01T00:00:00.000Z");
+            state.issue_map.insert(
+                String::from("my_issues"),
+                vec![unplanned, newest, make_issue("Oldest", "TEST-3")],
+            );
+            state.list_state.select(Some(0));
+        }
+
+        // the view's order comes first, then priority, then the newest first
+        app.handle_event(&create_key_event(KeyCode::Char('S')));
+        app.handle_event(&create_key_event(KeyCode::Char('S')));
+        assert_eq!(
+            identifiers(&app, "my_issues"),
+            ["TEST-1", "TEST-2", "TEST-3"]
+        );
+        let visible: Vec<String> = app
+            .current_issues()
+            .into_iter()
+            .map(|issue| issue.identifier)
+            .collect();
+        assert_eq!(visible, ["TEST-2", "TEST-1", "TEST-3"]);
+        assert_eq!(app.selected_issue().unwrap().identifier, "TEST-1");
+
+        app.handle_event(&create_key_event(KeyCode::Char('G')));
+        assert_eq!(app.selected_issue().unwrap().identifier, "TEST-1");
+        let mut terminal = Terminal::new(TestBackend::new(60, 12)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        // other tabs keep their own ordering
+        app.state.write().unwrap().selected_view_id = String::from("search_results");
+        assert_eq!(app.state.read().unwrap().ordering(), Default::default());
+    }
+
+    #[test]
+    fn test_insert_issue() {
+        let app = MyIssuesWidget::default();
+        app.state.write().unwrap().selected_view_id = String::from("my_issues");
+        app.insert_issue("my_issues", make_issue("Ticket One", "TEST-1"));
+        app.insert_issue("my_issues", make_issue("Ticket Two", "TEST-2"));
+
+        assert_eq!(app.state.read().unwrap().list_state.selected(), Some(0));
+        assert_eq!(app.selected_issue().unwrap().identifier, "TEST-2");
+
+        // issues created from another view don't move the selection
+        app.insert_issue("other_view", make_issue("Ticket Three", "TEST-3"));
+        assert_eq!(app.selected_issue().unwrap().identifier, "TEST-2");
+        assert_eq!(app.state.read().unwrap().issue_map["other_view"].len(), 1);
+    }
+
+    #[test]
+    fn test_update_blockers() {
+        let app = MyIssuesWidget::default();
+        let blocker = make_issue("Ticket One", "TEST-1");
+        let blocked = IssueFragment {
+            blockers: vec![IssueBlocker {
+                id: blocker.id.clone(),
+                state_type: String::from("started"),
+            }],
+            ..make_issue("Ticket Two", "TEST-2")
+        };
+        app.state
+            .write()
+            .unwrap()
+            .issue_map
+            .insert(String::from("my_issues"), vec![blocker.clone(), blocked]);
+        assert!(app.find_issue("TEST-2-id").unwrap().blocked());
+
+        // closing the blocker from another view unblocks the issue
+        let mut closed = blocker;
+        closed.state.type_ = String::from("completed");
+        app.apply_issue(&closed);
+        assert!(!app.find_issue("TEST-2-id").unwrap().blocked());
+    }
+
+    #[test]
+    fn test_merge_changes() {
+        let mut issues = vec![
+            make_issue("Ticket One", "TEST-1"),
+            make_issue("Ticket Two", "TEST-2"),
+            make_issue("Ticket Three", "TEST-3"),
+        ];
+        let updated = vec![
+            make_issue("Ticket Three renamed", "TEST-3"),
+            make_issue("Ticket Four", "TEST-4"),
+        ];
+        // TEST-1 changed but isn't in the view anymore
+        let changed_ids = vec![String::from("TEST-1-id"), String::from("TEST-3-id")];
+
+        merge_changes(&mut issues, updated, &changed_ids);
+
+        let titles: Vec<&str> = issues.iter().map(|issue| issue.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            ["Ticket Four", "Ticket Two", "Ticket Three renamed"]
+        );
+    }
+
+    fn identifiers(app: &MyIssuesWidget, view_id: &str) -> Vec<String> {
+        app.state.read().unwrap().issue_map[view_id]
+            .iter()
+            .map(|issue| issue.identifier.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_find_changes() {
+        let mut one = make_issue("Ticket One", "TEST-1");
+        one.id = String::from("issue-1");
+        let mut two = make_issue("Ticket Two", "TEST-2");
+        two.id = String::from("issue-2");
+        let previous = HashMap::from([
+            (one.id.clone(), one.updated_at.clone()),
+            (two.id.clone(), two.updated_at.clone()),
+        ]);
+        let mut updated = two.clone();
+        updated.updated_at = String::from("2025-07-11T10:00:00.000Z");
+        let mut three = make_issue("Ticket Three", "TEST-3");
+        three.id = String::from("issue-3");
+
+        assert_eq!(
+            find_changes(&previous, &[one, updated, three]),
+            [
+                (String::from("issue-2"), IssueChange::Updated),
+                (String::from("issue-3"), IssueChange::New)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_change_marks() {
+        let app = MyIssuesWidget::default();
+        {
+            let mut state = app.state.write().unwrap();
+            state.loading_state = crate::LoadingState::Loaded;
+            state.selected_view_id = String::from("my_issues");
+            let mut one = make_issue("Ticket One", "TEST-1");
+            one.id = String::from("issue-1");
+            let mut two = make_issue("Ticket Two", "TEST-2");
+            two.id = String::from("issue-2");
+            let mut three = make_issue("Ticket Three", "TEST-3");
+            three.id = String::from("issue-3");
+            state
+                .issue_map
+                .insert(String::from("my_issues"), vec![one, two, three]);
+            let until = std::time::Instant::now() + std::time::Duration::from_secs(60);
+            state
+                .changes
+                .insert(String::from("issue-1"), (IssueChange::New, until));
+            state
+                .changes
+                .insert(String::from("issue-2"), (IssueChange::Updated, until));
+            // marks that ran out aren't shown
+            state.changes.insert(
+                String::from("issue-3"),
+                (IssueChange::New, std::time::Instant::now()),
+            );
+        }
+        let mut terminal = Terminal::new(TestBackend::new(60, 8)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_my_issues() {
+        let server = StubServer::start();
+        let app = MyIssuesWidget::new(server.client(), Arc::default());
+
+        app.clone().fetch_my_issues().await;
+        assert_eq!(identifiers(&app, "my_issues"), ["LT-1", "LT-2"]);
+        assert_eq!(app.get_loading_state(), LoadingState::Loaded);
+        // LT-2 is blocked by an issue that is still in progress
+        let blocked: Vec<bool> = app.state.read().unwrap().issue_map["my_issues"]
+            .iter()
+            .map(|issue| issue.blocked())
+            .collect();
+        assert_eq!(blocked, [false, true]);
+        assert!(app.state.read().unwrap().changes.is_empty());
+
+        // the second load only asks for changes: LT-1 was updated, LT-3 is new and LT-2 left
+        // the view
+        app.clone().fetch_my_issues().await;
+        assert_eq!(identifiers(&app, "my_issues"), ["LT-3", "LT-1"]);
+        {
+            let state = app.state.read().unwrap();
+            assert_eq!(state.change("issue-1"), Some(IssueChange::Updated));
+            assert_eq!(state.change("issue-3"), Some(IssueChange::New));
+        }
+        assert_eq!(
+            server.operations(),
+            ["MyIssuesQuery", "MyIssuesUpdatedQuery", "IssueChangesQuery"]
+        );
+
+        // the next page skips LT-1, which the refresh already merged in
+        app.clone().load_more(String::from("my_issues"), None).await;
+        assert_eq!(identifiers(&app, "my_issues"), ["LT-3", "LT-1", "LT-2"]);
+
+        // a failed page stops showing as loading and says why
+        server.respond_with(
+            "MyIssuesQuery",
+            r#"{"errors": [{"message": "Rate limited"}]}"#,
+        );
+        app.state
+            .write()
+            .unwrap()
+            .page_info
+            .get_mut("my_issues")
+            .unwrap()
+            .loading_more = true;
+        app.clone().load_more(String::from("my_issues"), None).await;
+        let state = app.state.read().unwrap();
+        assert!(!state.page_info["my_issues"].loading_more);
+        assert_eq!(
+            state.notice.as_deref(),
+            Some("Couldn't load more issues: Rate limited")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_custom_view_and_search() {
+        let server = StubServer::start();
+        let app = MyIssuesWidget::new(server.client(), Arc::default());
+
+        app.clone()
+            .fetch_custom_view(custom_views_query::ViewFragment {
+                id: String::from("view-roadmap"),
+                name: String::from("Roadmap"),
+                ..Default::default()
+            })
+            .await;
+        assert_eq!(identifiers(&app, "view-roadmap"), ["LT-4"]);
+        assert!(app.state.read().unwrap().page_info["view-roadmap"].has_next_page);
+
+        app.clone().search_issues(String::from("login")).await;
+        assert_eq!(identifiers(&app, "search_results"), ["LT-1"]);
+        assert_eq!(
+            app.state.read().unwrap().page_info["search_results"].total_count,
+            Some(1)
+        );
+        assert_eq!(server.operations(), ["CustomViewQuery", "SearchQuery"]);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_project_issues() {
+        let server = StubServer::start();
+        let app = MyIssuesWidget::new(server.client(), Arc::default());
+
+        app.clone()
+            .load_view(String::from("project:project-1"))
+            .await;
+        assert_eq!(identifiers(&app, "project:project-1"), ["LT-4"]);
+        assert_eq!(
+            app.state.read().unwrap().selected_view_id,
+            "project:project-1"
+        );
+        assert_eq!(server.operations(), ["FilteredIssuesQuery"]);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_cycle_issues() {
+        let server = StubServer::start();
+        let app = MyIssuesWidget::new(server.client(), Arc::default());
+
+        app.clone().load_view(String::from("cycle:cycle-2")).await;
+        assert_eq!(identifiers(&app, "cycle:cycle-2"), ["LT-4"]);
+        assert_eq!(server.operations(), ["FilteredIssuesQuery"]);
+    }
+
+    #[test]
+    fn test_view_filter() {
+        assert_eq!(
+            serde_json::to_value(view_filter("project:project-1", None)).unwrap(),
+            json!({"project": {"id": {"eq": "project-1"}}})
+        );
+        assert_eq!(
+            serde_json::to_value(view_filter("cycle:cycle-2", Some(String::from("-PT1H"))))
+                .unwrap(),
+            json!({"cycle": {"id": {"eq": "cycle-2"}}, "updatedAt": {"gt": "-PT1H"}})
+        );
+        assert!(view_filter("custom-view-1", None).is_none());
+        assert!(is_cached("my_issues") && is_cached("custom-view-1"));
+        assert!(!is_cached("project:project-1") && !is_cached("cycle:cycle-2"));
+    }
+
+    #[tokio::test]
+    async fn test_edit_relations() {
+        let server = StubServer::start();
+        let app = MyIssuesWidget::new(server.client(), Arc::default());
+        let selected_issue_widget = widgets::SelectedIssueWidget::default();
+
+        // the typed identifier is looked up, then both issues are fetched again
+        app.clone()
+            .add_relation(
+                NewRelation {
+                    issue_id: String::from("issue-1"),
+                    kind: RelationKind::BlockedBy,
+                    target: String::from("LT-5"),
+                },
+                selected_issue_widget.clone(),
+            )
+            .await;
+        assert_eq!(
+            server.operations(),
+            [
+                "IssueQuery",
+                "IssueRelationCreateMutation",
+                "IssueQuery",
+                "IssueQuery"
+            ]
+        );
+        assert_eq!(app.state.read().unwrap().notice, None);
+
+        server.respond_with(
+            "IssueRelationDeleteMutation",
+            r#"{"errors": [{"message": "Entity not found"}]}"#,
+        );
+        app.clone()
+            .remove_relation(
+                String::from("issue-1"),
+                Relation {
+                    id: String::from("relation-3"),
+                    kind: RelationKind::BlockedBy,
+                    issue: Default::default(),
+                },
+                selected_issue_widget,
+            )
+            .await;
+        assert_eq!(
+            app.state.read().unwrap().notice.as_deref(),
+            Some("Couldn't remove relation: Entity not found")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_description() {
+        let server = StubServer::start();
+        let app = MyIssuesWidget::new(server.client(), Arc::default());
+        let mut selected_issue_widget = widgets::SelectedIssueWidget::default();
+        let mut issue = make_issue("Write migration", "LT-5");
+        issue.id = String::from("issue-5");
+        issue.updated_at = String::from("2025-07-11T12:00:00.000Z");
+        selected_issue_widget.set_selected_issue(Some(issue.clone()));
+
+        // nobody else touched the issue, so the edit is saved right away
+        app.clone()
+            .save_description(
+                issue.clone(),
+                String::from("Move the settings table\n\nand drop the old one"),
+                false,
+                selected_issue_widget.clone(),
+            )
+            .await;
+        assert_eq!(server.operations(), ["IssueQuery", "IssueUpdateMutation"]);
+        let saved = selected_issue_widget.selected_issue().unwrap();
+        assert_eq!(saved.updated_at, "2025-07-12T08:00:00.000Z");
+        assert!(!selected_issue_widget.has_pending_description());
+
+        // the issue changed in Linear after the editor was opened
+        issue.updated_at = String::from("2025-07-01T12:00:00.000Z");
+        app.clone()
+            .save_description(
+                issue,
+                String::from("Move the settings table"),
+                false,
+                selected_issue_widget.clone(),
+            )
+            .await;
+        assert_eq!(
+            server.operations(),
+            ["IssueQuery", "IssueUpdateMutation", "IssueQuery"]
+        );
+        let pending = selected_issue_widget.take_pending_description().unwrap();
+        assert_eq!(pending.issue.updated_at, "2025-07-11T12:00:00.000Z");
+
+        // confirming skips the check
+        app.clone()
+            .save_description(
+                pending.issue,
+                pending.description,
+                true,
+                selected_issue_widget,
+            )
+            .await;
+        assert_eq!(
+            server.operations(),
+            [
+                "IssueQuery",
+                "IssueUpdateMutation",
+                "IssueQuery",
+                "IssueUpdateMutation"
+            ]
+        );
+    }
+    #[tokio::test]
+    async fn test_create_issue_fails() {
+        let server = StubServer::start();
+        let app = MyIssuesWidget::new(server.client(), Arc::default());
+        let mut issue_form_widget = widgets::IssueFormWidget::default();
+        let input = issue_create_mutation::IssueCreateInput {
+            team_id: String::from("team-a"),
+            title: Some(String::from("New bug")),
+            ..Default::default()
+        };
+
+        // the form comes back with what was typed when the issue couldn't be created
+        server.respond_with(
+            "IssueCreateMutation",
+            r#"{"errors": [{"message": "Title is too long"}]}"#,
+        );
+        app.clone()
+            .create_issue_in_view(
+                String::from("my_issues"),
+                input.clone(),
+                widgets::SelectedIssueWidget::default(),
+                issue_form_widget.clone(),
+            )
+            .await;
+        issue_form_widget.reopen_if_failed();
+        assert!(issue_form_widget.visible);
+
+        // and when Linear doesn't return the new issue
+        issue_form_widget.close();
+        server.respond_with(
+            "IssueCreateMutation",
+            r#"{"data": {"issueCreate": {"success": false, "issue": null}}}"#,
+        );
+        app.clone()
+            .create_issue_in_view(
+                String::from("my_issues"),
+                input,
+                widgets::SelectedIssueWidget::default(),
+                issue_form_widget.clone(),
+            )
+            .await;
+        issue_form_widget.reopen_if_failed();
+        assert!(issue_form_widget.visible);
+        assert!(app.current_issues().is_empty());
+    }
 }
@@ -0,0 +1,267 @@
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, ListState, Paragraph, TableState, Widget, Wrap},
+};
+
+use crate::{
+    LoadingState,
+    config::{Action, Keymap},
+};
+
+pub(crate) const SELECTED_STYLE: Style = Style::new()
+    .fg(Color::Cyan)
+    .add_modifier(Modifier::BOLD)
+    .add_modifier(Modifier::ITALIC);
+
+// tests can't see the highlighting
+pub(crate) const HIGHLIGHT_SYMBOL: &str = if cfg!(test) { ">" } else { "" };
+
+// the selection of the list or the table the items are rendered with
+pub(crate) trait Selection: Default {
+    fn selected(&self) -> Option<usize>;
+    fn select(&mut self, index: Option<usize>);
+}
+
+impl Selection for ListState {
+    fn selected(&self) -> Option<usize> {
+        ListState::selected(self)
+    }
+
+    fn select(&mut self, index: Option<usize>) {
+        ListState::select(self, index)
+    }
+}
+
+impl Selection for TableState {
+    fn selected(&self) -> Option<usize> {
+        TableState::selected(self)
+    }
+
+    fn select(&mut self, index: Option<usize>) {
+        TableState::select(self, index)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ListTabState<T, S> {
+    pub loading_state: LoadingState,
+    pub items: Vec<T>,
+    pub selection: S,
+    pub notice: Option<String>,
+}
+
+impl<T, S: Selection> ListTabState<T, S> {
+    pub fn selected(&self) -> Option<&T> {
+        self.selection
+            .selected()
+            .and_then(|index| self.items.get(index))
+    }
+
+    // keeps the selection on the same item where possible
+    pub fn set_items(&mut self, items: Vec<T>, id: impl Fn(&T) -> &str) {
+        let selected_id = self.selected().map(|item| id(item).to_string());
+        let selected = selected_id
+            .and_then(|selected_id| items.iter().position(|item| id(item) == selected_id))
+            .or((!items.is_empty()).then_some(0));
+        self.items = items;
+        self.selection.select(selected);
+        self.loading_state = LoadingState::Loaded;
+        self.notice = None;
+    }
+
+    // a failed reload keeps showing the old items
+    pub fn set_error(&mut self, error: String) {
+        if self.items.is_empty() {
+            self.loading_state = LoadingState::Error(error);
+        } else {
+            self.loading_state = LoadingState::Loaded;
+            self.notice = Some(error);
+        }
+    }
+
+    fn move_selection(&mut self, forward: bool) {
+        let len = self.items.len();
+        if len == 0 {
+            return;
+        }
+        let next = match (self.selection.selected(), forward) {
+            (None, _) => 0,
+            (Some(index), true) => (index + 1).min(len - 1),
+            (Some(index), false) => index.saturating_sub(1),
+        };
+        self.selection.select(Some(next));
+    }
+
+    // draws the error or the loading screen in `block` and returns None, or hands the block back
+    // with the notice in its title once the items are loaded
+    pub fn render_status<'a>(
+        &self,
+        mut block: Block<'a>,
+        keymap: &Keymap,
+        area: Rect,
+        buf: &mut Buffer,
+    ) -> Option<Block<'a>> {
+        match &self.loading_state {
+            LoadingState::Loaded => {}
+            LoadingState::Error(e) => {
+                Paragraph::new(vec![
+                    Line::from("Error:\n\n".red().bold()),
+                    Line::from(e.clone().red().bold().underlined()),
+                    Line::from(vec![
+                        Span::from(format!("<{}> ", keymap.label(Action::Retry))).blue(),
+                        Span::from("to retry"),
+                    ]),
+                ])
+                .wrap(Wrap { trim: true })
+                .block(block)
+                .render(area, buf);
+                return None;
+            }
+            _ => {
+                block = block.title(Line::from("Loading…").right_aligned());
+                Paragraph::new("").block(block).render(area, buf);
+                return None;
+            }
+        }
+        if let Some(notice) = &self.notice {
+            block = block.title(Line::from(notice.clone().red()).right_aligned());
+        }
+        Some(block)
+    }
+}
+
+// what the inbox, the projects and the cycles tabs have in common: a list that's fetched when
+// the tab opens, takes the place of the issue list and the detail view while the tab is
+// selected, and in the projects and cycles tabs opens an item's issues in the issue list
+#[derive(Debug)]
+pub(crate) struct ListTab<T, S> {
+    state: Arc<RwLock<ListTabState<T, S>>>,
+    pub visible: bool,
+    // the selected item's issues are shown in the issue list, esc goes back to the list
+    pub in_item: bool,
+}
+
+impl<T, S: Selection> ListTab<T, S> {
+    pub fn read(&self) -> RwLockReadGuard<'_, ListTabState<T, S>> {
+        self.state.read().unwrap()
+    }
+
+    pub fn write(&self) -> RwLockWriteGuard<'_, ListTabState<T, S>> {
+        self.state.write().unwrap()
+    }
+
+    // returns whether a fetch should be started, which isn't the case while one is running
+    pub fn start_loading(&self) -> bool {
+        let mut state = self.write();
+        if state.loading_state == LoadingState::Loading {
+            return false;
+        }
+        // a reload keeps showing the old items
+        if state.items.is_empty() {
+            state.loading_state = LoadingState::Loading;
+        }
+        true
+    }
+
+    // returns whether the list has never been fetched
+    pub fn open(&mut self) -> bool {
+        self.visible = true;
+        self.in_item = false;
+        self.read().loading_state == LoadingState::Idle
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+        self.in_item = false;
+    }
+
+    pub fn back(&mut self) {
+        self.visible = true;
+        self.in_item = false;
+    }
+
+    // hides the list while the selected item's issues are shown
+    pub fn enter(&mut self) {
+        self.visible = false;
+        self.in_item = true;
+    }
+
+    // handles the keys all the list tabs share, returns whether the list should be fetched again
+    pub fn handle_action(&self, action: Option<Action>) -> bool {
+        match action {
+            Some(Action::Down) => self.write().move_selection(true),
+            Some(Action::Up) => self.write().move_selection(false),
+            Some(Action::Retry) => {
+                return matches!(self.read().loading_state, LoadingState::Error(_));
+            }
+            _ => {}
+        }
+        false
+    }
+}
+
+// a clone shares the state, like the widgets that hold it
+impl<T, S> Clone for ListTab<T, S> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            visible: self.visible,
+            in_item: self.in_item,
+        }
+    }
+}
+
+impl<T, S: Default> Default for ListTab<T, S> {
+    fn default() -> Self {
+        Self {
+            state: Arc::new(RwLock::new(ListTabState {
+                loading_state: LoadingState::default(),
+                items: Vec::new(),
+                selection: S::default(),
+                notice: None,
+            })),
+            visible: false,
+            in_item: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::widgets::ListState;
+
+    use crate::{LoadingState, config::Action};
+
+    use super::ListTab;
+
+    #[test]
+    fn test_list_tab() {
+        let mut list: ListTab<String, ListState> = ListTab::default();
+        assert!(list.open() && list.start_loading());
+        assert_eq!(list.read().loading_state, LoadingState::Loading);
+        assert!(!list.start_loading());
+
+        let items = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect();
+        list.write()
+            .set_items(items(&["a", "b", "c"]), String::as_str);
+        list.handle_action(Some(Action::Down));
+        assert_eq!(list.read().selected().map(String::as_str), Some("b"));
+
+        // a reload keeps the selected item and a failed one keeps the items
+        list.write().set_items(items(&["c", "b"]), String::as_str);
+        assert_eq!(list.read().selected().map(String::as_str), Some("b"));
+        list.write().set_error(String::from("offline"));
+        assert_eq!(list.read().loading_state, LoadingState::Loaded);
+        assert_eq!(list.read().notice.as_deref(), Some("offline"));
+        assert!(!list.handle_action(Some(Action::Retry)));
+
+        let empty: ListTab<String, ListState> = ListTab::default();
+        empty.write().set_error(String::from("offline"));
+        assert!(empty.handle_action(Some(Action::Retry)));
+    }
+}
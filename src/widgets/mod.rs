@@ -1,7 +1,51 @@
-mod selected_issue;
+mod board;
+mod cycles;
+mod inbox;
+mod issue_form;
 mod issue_list;
+mod list_tab;
+mod projects;
+mod relation_picker;
+mod selected_issue;
+mod state_picker;
 mod tab_widget;
 
-pub use selected_issue::SelectedIssueWidget;
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+
+pub use board::BoardWidget;
+pub use cycles::CyclesWidget;
+pub use inbox::InboxWidget;
+pub use issue_form::IssueFormWidget;
 pub use issue_list::MyIssuesWidget;
+pub use projects::ProjectsWidget;
+pub use relation_picker::{NewRelation, Relation, RelationPickerWidget};
+pub(crate) use relation_picker::{RelationKind, relations};
+pub use selected_issue::{PendingDescription, SelectedIssueWidget};
+pub(crate) use state_picker::STATE_TYPE_ORDER;
+pub use state_picker::StatePickerWidget;
 pub use tab_widget::TabWidget;
+
+#[cfg(test)]
+pub(crate) use selected_issue::tests::make_issue;
+#[cfg(test)]
+pub(crate) use state_picker::tests::make_state;
+
+// a plain key press, as the widgets get it from the terminal
+#[cfg(test)]
+pub(crate) fn create_key_event(code: crossterm::event::KeyCode) -> crossterm::event::Event {
+    crossterm::event::Event::Key(crossterm::event::KeyEvent {
+        code,
+        kind: crossterm::event::KeyEventKind::Press,
+        modifiers: crossterm::event::KeyModifiers::empty(),
+        state: crossterm::event::KeyEventState::empty(),
+    })
+}
+
+// centered area for popups, sized as a percentage of `area`
+pub(crate) fn popup_area(area: Rect, percent_x: u16, percent_y: u16) -> Rect {
+    let vertical = Layout::vertical([Constraint::Percentage(percent_y)]).flex(Flex::Center);
+    let horizontal = Layout::horizontal([Constraint::Percentage(percent_x)]).flex(Flex::Center);
+    let [area] = vertical.areas(area);
+    let [area] = horizontal.areas(area);
+    area
+}
@@ -0,0 +1,263 @@
+use std::{str::FromStr, sync::Arc};
+
+use crossterm::event::{Event, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Row, StatefulWidget, Table, TableState, Widget},
+};
+
+use crate::{
+    LtEvent,
+    api::LinearClient,
+    config::{Action, Keymap},
+    iconmap,
+    queries::{
+        ProjectsQuery,
+        projects_query::{self, ProjectFragment, ProjectStatusType},
+    },
+    widgets::list_tab::{HIGHLIGHT_SYMBOL, ListTab, SELECTED_STYLE},
+};
+
+// projects being worked on come first, finished ones last
+fn status_rank(status_type: &ProjectStatusType) -> usize {
+    match status_type {
+        ProjectStatusType::started => 0,
+        ProjectStatusType::planned => 1,
+        ProjectStatusType::paused => 2,
+        ProjectStatusType::backlog => 3,
+        ProjectStatusType::completed => 4,
+        ProjectStatusType::canceled => 5,
+        ProjectStatusType::Other(_) => 6,
+    }
+}
+
+// progress as a bar of `width` cells and a percentage
+fn progress_bar(progress: f64, width: usize) -> String {
+    let progress = progress.clamp(0.0, 1.0);
+    let filled = (progress * width as f64).round() as usize;
+    format!(
+        "{}{} {:>3}%",
+        "▰".repeat(filled),
+        "▱".repeat(width - filled),
+        (progress * 100.0).round()
+    )
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProjectsWidget {
+    // the list of projects takes the place of the issue list and the detail view
+    list: ListTab<ProjectFragment, TableState>,
+    client: Arc<LinearClient>,
+    keymap: Arc<Keymap>,
+}
+
+impl ProjectsWidget {
+    pub fn new(client: Arc<LinearClient>, keymap: Arc<Keymap>) -> Self {
+        Self {
+            client,
+            keymap,
+            ..Default::default()
+        }
+    }
+
+    pub fn visible(&self) -> bool {
+        self.list.visible
+    }
+
+    // a project's issues are shown in the issue list, esc goes back to the projects
+    pub fn in_project(&self) -> bool {
+        self.list.in_item
+    }
+
+    pub fn run(&self) {
+        if self.list.start_loading() {
+            tokio::spawn(self.clone().fetch());
+        }
+    }
+
+    pub fn open(&mut self) {
+        if self.list.open() {
+            self.run();
+        }
+    }
+
+    pub fn close(&mut self) {
+        self.list.close();
+    }
+
+    pub fn back(&mut self) {
+        self.list.back();
+    }
+
+    async fn fetch(self) {
+        let client = &self.client;
+        let variables = projects_query::Variables {};
+        match client.query(ProjectsQuery, variables).await {
+            Ok(data) => self.set_projects(data.projects.nodes),
+            Err(e) => self.list.write().set_error(e.to_string()),
+        }
+    }
+
+    fn set_projects(&self, mut projects: Vec<ProjectFragment>) {
+        // a stable sort keeps the most recently updated first within a status
+        projects.sort_by_key(|project| status_rank(&project.status.type_));
+        self.list
+            .write()
+            .set_items(projects, |project| project.id.as_str());
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> LtEvent<'_> {
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            // nothing else scrolls while the projects are shown
+            let action = match self.keymap.action(key) {
+                Some(Action::ScrollDown) => Some(Action::Down),
+                Some(Action::ScrollUp) => Some(Action::Up),
+                action => action,
+            };
+            if self.list.handle_action(action) {
+                self.run();
+            }
+            match action {
+                Some(Action::OpenUrl) => {
+                    if let Some(project) = self.list.read().selected() {
+                        let _ = open::that(&project.url);
+                    }
+                }
+                Some(Action::Select) => {
+                    let project_id = self
+                        .list
+                        .read()
+                        .selected()
+                        .map(|project| project.id.clone());
+                    if let Some(project_id) = project_id {
+                        self.list.enter();
+                        return LtEvent::OpenProject(project_id);
+                    }
+                }
+                _ => {}
+            }
+        }
+        LtEvent::None
+    }
+}
+
+fn project_row(project: &ProjectFragment) -> Row<'static> {
+    let color = |color: &str| Color::from_str(color).unwrap_or(Color::Reset);
+    let icon = project
+        .icon
+        .as_deref()
+        .map(iconmap::ico_to_nf)
+        .unwrap_or_default();
+    let lead = match &project.lead {
+        Some(lead) if lead.is_me => Span::from("You"),
+        Some(lead) => Span::from(lead.display_name.clone()),
+        None => Span::from("No lead").dark_gray(),
+    };
+    Row::new(vec![
+        Line::from(Span::from(format!("{}{}", icon, project.name)).fg(color(&project.color))),
+        Line::from(Span::from(project.status.name.clone()).fg(color(&project.status.color))),
+        Line::from(lead),
+        Line::from(project.target_date.clone().unwrap_or_default()),
+        Line::from(progress_bar(project.progress, 10)),
+    ])
+}
+
+impl Widget for &ProjectsWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut state = self.list.write();
+        let block = Block::bordered().title_bottom(Line::from(vec![
+            Span::from(format!(
+                " <{}/{}> ",
+                self.keymap.label(Action::Down),
+                self.keymap.label(Action::Up)
+            ))
+            .blue(),
+            Span::from("to select "),
+            Span::from("─"),
+            Span::from(format!(" <{}> ", self.keymap.label(Action::Select))).blue(),
+            Span::from("to show issues "),
+            Span::from("─"),
+            Span::from(format!(" <{}> ", self.keymap.label(Action::OpenUrl))).blue(),
+            Span::from("to open in Linear"),
+        ]));
+
+        let Some(mut block) = state.render_status(block, &self.keymap, area, buf) else {
+            return;
+        };
+        block = block.title(Line::from(format!(" {} projects ", state.items.len())).dark_gray());
+        if state.items.is_empty() {
+            Paragraph::new("No projects".dark_gray())
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+
+        let rows: Vec<Row> = state.items.iter().map(project_row).collect();
+        let header = Row::new(["Project", "Status", "Lead", "Target", "Progress"])
+            .dark_gray()
+            .bold();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Fill(1),
+                Constraint::Length(12),
+                Constraint::Length(16),
+                Constraint::Length(10),
+                Constraint::Length(15),
+            ],
+        )
+        .header(header)
+        .row_highlight_style(SELECTED_STYLE)
+        .highlight_symbol(HIGHLIGHT_SYMBOL)
+        .block(block);
+        StatefulWidget::render(table, area, buf, &mut state.selection);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crossterm::event::KeyCode;
+    use insta::assert_snapshot;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use crate::{LtEvent, stub_server::StubServer, widgets::create_key_event};
+
+    use super::{ProjectsWidget, progress_bar};
+
+    #[test]
+    fn test_progress_bar() {
+        assert_eq!(progress_bar(0.0, 4), "▱▱▱▱   0%");
+        assert_eq!(progress_bar(0.5, 4), "▰▰▱▱  50%");
+        assert_eq!(progress_bar(1.2, 4), "▰▰▰▰ 100%");
+    }
+
+    #[tokio::test]
+    async fn test_projects() {
+        let server = StubServer::start();
+        let mut app = ProjectsWidget::new(server.client(), Arc::default());
+        app.clone().fetch().await;
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 8)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&app, frame.area()))
+            .unwrap();
+        // started projects are listed first
+        assert_snapshot!(terminal.backend());
+
+        app.handle_event(&create_key_event(KeyCode::Char('j')));
+        assert_eq!(
+            app.handle_event(&create_key_event(KeyCode::Enter)),
+            LtEvent::OpenProject(String::from("project-1"))
+        );
+        assert!(!app.visible() && app.in_project());
+        app.back();
+        assert!(app.visible() && !app.in_project());
+    }
+}
@@ -0,0 +1,540 @@
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Clear, List, ListItem, ListState, Paragraph, StatefulWidget, Widget},
+};
+use tui_input::Input;
+use tui_input::backend::crossterm::EventHandler;
+
+use crate::{
+    IssueFragment, LtEvent,
+    fuzzy::fuzzy_match,
+    iconmap,
+    queries::{
+        issue_relation_create_mutation::{IssueRelationCreateInput, IssueRelationType},
+        issue_relations_query::{self, RelatedIssueFragment},
+    },
+    widgets::popup_area,
+};
+
+// how many matching issues the picker lists
+const MAX_MATCHES: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelationKind {
+    BlockedBy,
+    Blocks,
+    DuplicateOf,
+    DuplicatedBy,
+    Related,
+    Similar,
+}
+
+impl RelationKind {
+    // the order the sidebar lists them in
+    pub const ALL: [RelationKind; 6] = [
+        RelationKind::BlockedBy,
+        RelationKind::Blocks,
+        RelationKind::DuplicateOf,
+        RelationKind::DuplicatedBy,
+        RelationKind::Related,
+        RelationKind::Similar,
+    ];
+
+    // similar relations are suggested by Linear rather than made by hand
+    const CREATABLE: [RelationKind; 5] = [
+        RelationKind::BlockedBy,
+        RelationKind::Blocks,
+        RelationKind::Related,
+        RelationKind::DuplicateOf,
+        RelationKind::DuplicatedBy,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RelationKind::BlockedBy => "Blocked by",
+            RelationKind::Blocks => "Blocks",
+            RelationKind::DuplicateOf => "Duplicate of",
+            RelationKind::DuplicatedBy => "Duplicated by",
+            RelationKind::Related => "Related",
+            RelationKind::Similar => "Similar",
+        }
+    }
+
+    // `inverse` is set for relations that point at the issue rather than away from it
+    fn from_type(type_: &str, inverse: bool) -> Option<Self> {
+        match (type_, inverse) {
+            ("blocks", false) => Some(RelationKind::Blocks),
+            ("blocks", true) => Some(RelationKind::BlockedBy),
+            ("duplicate", false) => Some(RelationKind::DuplicateOf),
+            ("duplicate", true) => Some(RelationKind::DuplicatedBy),
+            ("related", _) => Some(RelationKind::Related),
+            ("similar", _) => Some(RelationKind::Similar),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Relation {
+    pub id: String,
+    pub kind: RelationKind,
+    pub issue: RelatedIssueFragment,
+}
+
+// both directions of an issue's relations, in the order of `RelationKind::ALL`
+pub fn relations(issue: issue_relations_query::IssueRelationsQueryIssue) -> Vec<Relation> {
+    let outgoing = issue.relations.nodes.into_iter().filter_map(|node| {
+        Some(Relation {
+            kind: RelationKind::from_type(&node.type_, false)?,
+            id: node.id,
+            issue: node.related_issue,
+        })
+    });
+    let incoming = issue
+        .inverse_relations
+        .nodes
+        .into_iter()
+        .filter_map(|node| {
+            Some(Relation {
+                kind: RelationKind::from_type(&node.type_, true)?,
+                id: node.id,
+                issue: node.issue,
+            })
+        });
+    let mut relations: Vec<Relation> = outgoing.chain(incoming).collect();
+    relations.sort_by_key(|relation| {
+        RelationKind::ALL
+            .iter()
+            .position(|kind| *kind == relation.kind)
+    });
+    relations
+}
+
+// a relation to create from `issue_id` to `target`, which is an issue id or an identifier
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewRelation {
+    pub issue_id: String,
+    pub kind: RelationKind,
+    pub target: String,
+}
+
+impl NewRelation {
+    // Linear stores "blocked by" and "duplicated by" as the other issue's relation
+    pub fn input(&self, target_id: String) -> IssueRelationCreateInput {
+        let (issue_id, related_issue_id, type_) = match self.kind {
+            RelationKind::Blocks => (self.issue_id.clone(), target_id, IssueRelationType::blocks),
+            RelationKind::BlockedBy => {
+                (target_id, self.issue_id.clone(), IssueRelationType::blocks)
+            }
+            RelationKind::DuplicateOf => (
+                self.issue_id.clone(),
+                target_id,
+                IssueRelationType::duplicate,
+            ),
+            RelationKind::DuplicatedBy => (
+                target_id,
+                self.issue_id.clone(),
+                IssueRelationType::duplicate,
+            ),
+            RelationKind::Related => (self.issue_id.clone(), target_id, IssueRelationType::related),
+            RelationKind::Similar => (self.issue_id.clone(), target_id, IssueRelationType::similar),
+        };
+        IssueRelationCreateInput {
+            id: None,
+            issue_id,
+            related_issue_id,
+            type_,
+        }
+    }
+}
+
+// ENG-123, which may not be in any of the loaded lists
+fn looks_like_identifier(text: &str) -> bool {
+    text.split_once('-').is_some_and(|(team, number)| {
+        !team.is_empty()
+            && team.chars().all(|c| c.is_ascii_alphanumeric())
+            && !number.is_empty()
+            && number.chars().all(|c| c.is_ascii_digit())
+    })
+}
+
+enum Row<'a> {
+    Existing(&'a Relation),
+    Candidate(&'a IssueFragment),
+    Identifier(String),
+}
+
+#[derive(Debug, Default)]
+struct RelationPickerWidgetState {
+    issue: Option<IssueFragment>,
+    relations: Vec<Relation>,
+    // loaded issues that can be picked without typing their identifier
+    candidates: Vec<IssueFragment>,
+    kind_index: usize,
+    input: Input,
+    list_state: ListState,
+}
+
+impl RelationPickerWidgetState {
+    fn kind(&self) -> RelationKind {
+        RelationKind::CREATABLE[self.kind_index]
+    }
+
+    // the relations to remove while nothing is typed, otherwise the issues matching the input
+    fn rows(&self) -> Vec<Row<'_>> {
+        let query = self.input.value().trim();
+        if query.is_empty() {
+            return self.relations.iter().map(Row::Existing).collect();
+        }
+        let mut matches: Vec<(i64, &IssueFragment)> = self
+            .candidates
+            .iter()
+            .filter_map(|issue| {
+                let text = format!("{} {}", issue.identifier, issue.title);
+                fuzzy_match(query, &text).map(|(score, _)| (score, issue))
+            })
+            .collect();
+        matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        let mut rows = vec![];
+        let identifier = query.to_uppercase();
+        if looks_like_identifier(query)
+            && !matches
+                .iter()
+                .any(|(_, issue)| issue.identifier == identifier)
+        {
+            rows.push(Row::Identifier(identifier));
+        }
+        rows.extend(
+            matches
+                .into_iter()
+                .take(MAX_MATCHES)
+                .map(|(_, issue)| Row::Candidate(issue)),
+        );
+        rows
+    }
+
+    fn highlighted(&self) -> Option<Row<'_>> {
+        self.list_state
+            .selected()
+            .and_then(|index| self.rows().into_iter().nth(index))
+    }
+
+    fn reset_selection(&mut self) {
+        let selected = if self.rows().is_empty() {
+            None
+        } else {
+            Some(0)
+        };
+        self.list_state.select(selected);
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RelationPickerWidget {
+    state: Arc<RwLock<RelationPickerWidgetState>>,
+    pub visible: bool,
+}
+
+impl RelationPickerWidget {
+    pub fn open(
+        &mut self,
+        issue: IssueFragment,
+        relations: Vec<Relation>,
+        mut candidates: Vec<IssueFragment>,
+    ) {
+        self.visible = true;
+        candidates.retain(|candidate| candidate.id != issue.id);
+        let mut state = self.state.write().unwrap();
+        state.issue = Some(issue);
+        state.relations = relations;
+        state.candidates = candidates;
+        state.kind_index = 0;
+        state.input.reset();
+        state.reset_selection();
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> LtEvent<'_> {
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            let mut state = self.state.write().unwrap();
+            let Some(issue_id) = state.issue.as_ref().map(|issue| issue.id.clone()) else {
+                return LtEvent::None;
+            };
+            let len = state.rows().len();
+            match key.code {
+                KeyCode::Esc => {
+                    drop(state);
+                    self.close();
+                }
+                KeyCode::Down if len > 0 => {
+                    let next = state.list_state.selected().map_or(0, |i| (i + 1) % len);
+                    state.list_state.select(Some(next));
+                }
+                KeyCode::Up if len > 0 => {
+                    let prev = state
+                        .list_state
+                        .selected()
+                        .map_or(len - 1, |i| (i + len - 1) % len);
+                    state.list_state.select(Some(prev));
+                }
+                KeyCode::Tab | KeyCode::Right => {
+                    state.kind_index = (state.kind_index + 1) % RelationKind::CREATABLE.len();
+                }
+                KeyCode::BackTab | KeyCode::Left => {
+                    let len = RelationKind::CREATABLE.len();
+                    state.kind_index = (state.kind_index + len - 1) % len;
+                }
+                KeyCode::Enter => {
+                    let target = match state.highlighted() {
+                        Some(Row::Candidate(issue)) => issue.id.clone(),
+                        Some(Row::Identifier(identifier)) => identifier,
+                        _ => return LtEvent::None,
+                    };
+                    let kind = state.kind();
+                    drop(state);
+                    self.close();
+                    return LtEvent::CreateRelation(NewRelation {
+                        issue_id,
+                        kind,
+                        target,
+                    });
+                }
+                KeyCode::Delete => {
+                    if let Some(Row::Existing(relation)) = state.highlighted() {
+                        let relation = relation.clone();
+                        drop(state);
+                        self.close();
+                        return LtEvent::DeleteRelation(issue_id, relation);
+                    }
+                }
+                _ => {
+                    state.input.handle_event(event);
+                    state.reset_selection();
+                }
+            }
+        }
+        LtEvent::None
+    }
+}
+
+fn issue_spans(identifier: &str, title: &str, state_type: &str, color: &str) -> Vec<Span<'static>> {
+    let color = Color::from_str(color).unwrap_or(Color::White);
+    vec![
+        Span::from(iconmap::state_to_nf(state_type)).fg(color),
+        Span::from(" "),
+        Span::from(identifier.to_owned()).blue().bold(),
+        Span::from(" "),
+        Span::from(title.to_owned()),
+    ]
+}
+
+impl Widget for &RelationPickerWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if !self.visible {
+            return;
+        }
+        let area = popup_area(area, 70, 60);
+        Clear.render(area, buf);
+
+        let mut state = self.state.write().unwrap();
+        let identifier = state
+            .issue
+            .as_ref()
+            .map_or(String::new(), |issue| issue.identifier.clone());
+        let block = Block::bordered()
+            .title(Line::from(vec![
+                Span::from(format!(" {} ", identifier)).blue().bold(),
+                Span::from("Relations "),
+            ]))
+            .title_bottom(Line::from(vec![
+                Span::from(" <enter> ").blue(),
+                Span::from("to add "),
+                Span::from("─"),
+                Span::from(" <del> ").blue(),
+                Span::from("to remove "),
+                Span::from("─"),
+                Span::from(" <esc> ").blue(),
+                Span::from("to cancel "),
+            ]));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let [kind_area, input_area, list_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Min(0),
+        ])
+        .areas(inner);
+
+        Paragraph::new(Line::from(vec![
+            Span::from("Relation: "),
+            Span::from(format!("‹{}›", state.kind().label()))
+                .yellow()
+                .bold(),
+            Span::from(" <tab> ").blue(),
+            Span::from("to change").dark_gray(),
+        ]))
+        .render(kind_area, buf);
+        Paragraph::new(Line::from(vec![
+            Span::from("Issue: "),
+            Span::from(state.input.value().to_owned() + "|").yellow(),
+        ]))
+        .render(input_area, buf);
+
+        let rows = state.rows();
+        if rows.is_empty() {
+            let text = if state.input.value().trim().is_empty() {
+                "No relations yet, type an identifier or title to add one"
+            } else {
+                "No matching issues"
+            };
+            Paragraph::new(text.dark_gray()).render(list_area, buf);
+            return;
+        }
+        let items: Vec<ListItem> = rows
+            .into_iter()
+            .map(|row| match row {
+                Row::Existing(relation) => {
+                    let mut spans = vec![Span::from(format!("{}: ", relation.kind.label()))];
+                    spans.extend(issue_spans(
+                        &relation.issue.identifier,
+                        &relation.issue.title,
+                        &relation.issue.state.type_,
+                        &relation.issue.state.color,
+                    ));
+                    ListItem::new(Line::from(spans))
+                }
+                Row::Candidate(issue) => ListItem::new(Line::from(issue_spans(
+                    &issue.identifier,
+                    &issue.title,
+                    &issue.state.type_,
+                    &issue.state.color,
+                ))),
+                Row::Identifier(identifier) => ListItem::new(Line::from(vec![
+                    Span::from("Use "),
+                    Span::from(identifier).blue().bold(),
+                ])),
+            })
+            .collect();
+
+        // tests can't see the highlighting
+        let highlight_symbol = if cfg!(test) { ">" } else { "" };
+
+        let list = List::new(items)
+            .highlight_style(Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+            .highlight_symbol(highlight_symbol);
+        StatefulWidget::render(list, list_area, buf, &mut state.list_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+    use insta::assert_snapshot;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use crate::{
+        LtEvent,
+        queries::{
+            issue_relation_create_mutation::IssueRelationType,
+            issue_relations_query::{RelatedIssueFragment, RelatedIssueFragmentState},
+        },
+        widgets::{create_key_event, make_issue},
+    };
+
+    use super::{NewRelation, Relation, RelationKind, RelationPickerWidget};
+
+    fn type_text(app: &mut RelationPickerWidget, text: &str) {
+        for c in text.chars() {
+            app.handle_event(&create_key_event(KeyCode::Char(c)));
+        }
+    }
+
+    fn open(app: &mut RelationPickerWidget) {
+        let blocker = Relation {
+            id: String::from("relation-1"),
+            kind: RelationKind::BlockedBy,
+            issue: RelatedIssueFragment {
+                id: String::from("TEST-9-id"),
+                identifier: String::from("TEST-9"),
+                title: String::from("Rotate keys"),
+                state: RelatedIssueFragmentState {
+                    name: String::from("In Progress"),
+                    color: String::from("#f2c94c"),
+                    type_: String::from("started"),
+                },
+            },
+        };
+        app.open(
+            make_issue("Testing Ticket", "TEST-1"),
+            vec![blocker],
+            vec![
+                make_issue("Testing Ticket", "TEST-1"),
+                make_issue("Login page", "TEST-2"),
+                make_issue("Settings", "TEST-3"),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_relation_picker() {
+        let mut app = RelationPickerWidget::default();
+        open(&mut app);
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        match app.handle_event(&create_key_event(KeyCode::Delete)) {
+            LtEvent::DeleteRelation(issue_id, relation) => {
+                assert_eq!(issue_id, "TEST-1-id");
+                assert_eq!(relation.id, "relation-1");
+            }
+            ev => panic!("expected DeleteRelation, got {:?}", ev),
+        }
+        assert!(!app.visible);
+
+        // the issue itself isn't offered
+        open(&mut app);
+        type_text(&mut app, "test");
+        assert_eq!(app.state.read().unwrap().rows().len(), 2);
+        app.handle_event(&create_key_event(KeyCode::Tab));
+        type_text(&mut app, "log");
+        let ev = app.handle_event(&create_key_event(KeyCode::Enter));
+        assert_eq!(
+            ev,
+            LtEvent::CreateRelation(NewRelation {
+                issue_id: String::from("TEST-1-id"),
+                kind: RelationKind::Blocks,
+                target: String::from("TEST-2-id"),
+            })
+        );
+
+        // an identifier that isn't loaded is passed on as typed
+        open(&mut app);
+        type_text(&mut app, "eng-42");
+        let ev = app.handle_event(&create_key_event(KeyCode::Enter));
+        let LtEvent::CreateRelation(relation) = ev else {
+            panic!("expected CreateRelation, got {:?}", ev);
+        };
+        assert_eq!(relation.kind, RelationKind::BlockedBy);
+        assert_eq!(relation.target, "ENG-42");
+        let input = relation.input(String::from("ENG-42-id"));
+        assert_eq!(input.issue_id, "ENG-42-id");
+        assert_eq!(input.related_issue_id, "TEST-1-id");
+        assert_eq!(input.type_, IssueRelationType::blocks);
+    }
+}
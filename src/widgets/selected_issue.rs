@@ -1,6 +1,19 @@
+use crate::CommentFragment;
 use crate::IssueFragment;
+use crate::LoadingState;
 use crate::LtEvent;
+use crate::api::{ApiError, LinearClient};
+use crate::config::{Action, Keymap};
+use crate::editor;
+use crate::git;
 use crate::iconmap;
+use crate::queries::{
+    CommentCreateMutation, IssueCommentsQuery, IssueHierarchyQuery, IssueQuery,
+    IssueRelationsQuery, IssueVcsBranchSearchQuery, comment_create_mutation, issue_comments_query,
+    issue_hierarchy_query::{self, SubIssueFragment},
+    issue_query, issue_relations_query, issue_vcs_branch_search_query,
+};
+use crate::widgets::{Relation, RelationKind, relations};
 
 use ratatui::style::Modifier;
 use ratatui::style::Style;
@@ -12,6 +25,7 @@ use ratatui::widgets::StatefulWidget;
 use ratatui::widgets::Wrap;
 use std::str::FromStr;
 use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
 use chrono::DateTime;
 
@@ -24,52 +38,815 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Widget},
 };
+use tui_input::Input;
+use tui_input::backend::crossterm::EventHandler;
+
+// the selection has to rest on an issue this long before its sub-issues and relations are fetched
+const HIERARCHY_DELAY: Duration = Duration::from_millis(150);
+
+#[derive(Debug, Clone)]
+struct SubIssue {
+    issue: SubIssueFragment,
+    // closed and total count of its own sub-issues
+    progress: (usize, usize),
+    expanded: bool,
+    // None until it is expanded for the first time
+    children: Option<Vec<SubIssue>>,
+}
+
+fn is_closed(state_type: &str) -> bool {
+    matches!(state_type, "completed" | "cancelled")
+}
+
+fn sub_issues(children: issue_hierarchy_query::IssueHierarchyQueryIssueChildren) -> Vec<SubIssue> {
+    children
+        .nodes
+        .into_iter()
+        .map(|node| SubIssue {
+            progress: (
+                node.children
+                    .nodes
+                    .iter()
+                    .filter(|child| is_closed(&child.state.type_))
+                    .count(),
+                node.children.nodes.len(),
+            ),
+            issue: node.sub_issue_fragment,
+            expanded: false,
+            children: None,
+        })
+        .collect()
+}
+
+fn find_sub_issue<'a>(nodes: &'a mut [SubIssue], issue_id: &str) -> Option<&'a mut SubIssue> {
+    for node in nodes {
+        if node.issue.id == issue_id {
+            return Some(node);
+        }
+        if let Some(found) = node
+            .children
+            .as_deref_mut()
+            .and_then(|children| find_sub_issue(children, issue_id))
+        {
+            return Some(found);
+        }
+    }
+    None
+}
+
+// the rows of the tree that are currently visible, with their depth
+fn tree_rows(nodes: &[SubIssue], depth: usize, rows: &mut Vec<(usize, SubIssue)>) {
+    for node in nodes {
+        rows.push((depth, node.clone()));
+        if node.expanded
+            && let Some(children) = &node.children
+        {
+            tree_rows(children, depth + 1, rows);
+        }
+    }
+}
+
+// a description edit that waits for confirmation, because the issue changed in Linear while
+// it was being edited. `issue` is the issue as it is in Linear now.
+#[derive(Debug, Clone)]
+pub struct PendingDescription {
+    pub issue: IssueFragment,
+    pub description: String,
+}
 
 #[derive(Debug, Default)]
 struct SelectedIssueWidgetState {
     selected_issue: Option<IssueFragment>,
+    show_comments: bool,
+    comments_loading_state: LoadingState,
+    comments: Vec<CommentFragment>,
+    reply_error: Option<String>,
+    hierarchy_loading_state: LoadingState,
+    parent: Option<SubIssueFragment>,
+    sub_issues: Vec<SubIssue>,
+    // the highlighted row of the sub-issue tree
+    sub_issue_cursor: Option<usize>,
+    // a parent or sub-issue that was opened and is still being fetched
+    opening: Option<SubIssueFragment>,
+    notice: Option<String>,
+    relations_loading_state: LoadingState,
+    relations: Vec<Relation>,
+    pending_description: Option<PendingDescription>,
+    // the outcome of something done to the issue outside of Linear, like checking out its branch
+    message: Option<String>,
+}
+
+impl SelectedIssueWidgetState {
+    fn visible_sub_issues(&self) -> Vec<(usize, SubIssue)> {
+        let mut rows = vec![];
+        tree_rows(&self.sub_issues, 0, &mut rows);
+        rows
+    }
+
+    fn highlighted_sub_issue(&self) -> Option<SubIssue> {
+        let cursor = self.sub_issue_cursor?;
+        self.visible_sub_issues()
+            .into_iter()
+            .nth(cursor)
+            .map(|(_, sub_issue)| sub_issue)
+    }
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct SelectedIssueWidget {
     state: Arc<RwLock<SelectedIssueWidgetState>>,
+    client: Arc<LinearClient>,
+    keymap: Arc<Keymap>,
     scroll_state: ScrollbarState,
     scroll: usize,
+    reply_input: Input,
+    pub replying: bool,
 }
 
 impl SelectedIssueWidget {
+    pub fn new(client: Arc<LinearClient>, keymap: Arc<Keymap>) -> Self {
+        Self {
+            client,
+            keymap,
+            ..Default::default()
+        }
+    }
+
     pub fn set_selected_issue(&mut self, issue: Option<IssueFragment>) {
-        self.state.write().unwrap().selected_issue = issue;
         self.scroll = 0;
         self.scroll_state = ScrollbarState::default();
+        self.replying = false;
+        self.show_issue(issue);
+    }
+
+    fn show_issue(&self, issue: Option<IssueFragment>) {
+        let fetch_comments = {
+            let mut state = self.state.write().unwrap();
+            state.selected_issue = issue;
+            state.comments.clear();
+            state.reply_error = None;
+            state.comments_loading_state = LoadingState::Idle;
+            state.parent = None;
+            state.sub_issues.clear();
+            state.sub_issue_cursor = None;
+            state.opening = None;
+            state.notice = None;
+            state.message = None;
+            state.relations.clear();
+            state.show_comments
+        };
+        if fetch_comments {
+            self.load_comments();
+        }
+        self.load_hierarchy();
+        self.load_relations();
     }
 
-    pub fn handle_event(&mut self, event: &Event) -> LtEvent {
-        if let Event::Key(key) = event {
-            if key.kind == KeyEventKind::Press {
+    // shows a parent or sub-issue, which doesn't have to be in the issue list
+    fn open_issue(&mut self, issue: SubIssueFragment) {
+        self.scroll = 0;
+        self.scroll_state = ScrollbarState::default();
+        self.replying = false;
+        let issue_id = issue.id.clone();
+        self.state.write().unwrap().opening = Some(issue);
+        tokio::spawn(self.clone().fetch_issue(issue_id));
+    }
+
+    async fn fetch_issue(self, issue_id: String) {
+        let client = &self.client;
+        let variables = issue_query::Variables {
+            id: issue_id.clone(),
+        };
+        let result = client.query(IssueQuery, variables).await;
+        let mut state = self.state.write().unwrap();
+        if state.opening.as_ref().map(|issue| &issue.id) != Some(&issue_id) {
+            return;
+        }
+        match result {
+            Ok(data) => {
+                drop(state);
+                self.show_issue(Some(data.issue.into()));
+            }
+            Err(e) => {
+                state.opening = None;
+                state.notice = Some(format!("Couldn't open issue: {}", e));
+            }
+        }
+    }
+
+    // shows the issue of a git branch, unless an issue was selected in the meantime
+    pub fn open_branch_issue(&self, branch: String) {
+        tokio::spawn(self.clone().fetch_branch_issue(branch));
+    }
+
+    async fn fetch_branch_issue(self, branch: String) {
+        let result = self.find_branch_issue(&branch).await;
+        if self.state.read().unwrap().selected_issue.is_some() {
+            return;
+        }
+        match result {
+            Ok(Some(issue)) => {
+                self.show_issue(Some(issue));
+                self.set_message(format!("Current branch: {}", branch));
+            }
+            Ok(None) => {}
+            Err(e) => self.set_notice(format!("Couldn't look up the issue of {}: {}", branch, e)),
+        }
+    }
+
+    // the issue Linear linked the branch to, or else the one its name starts with
+    async fn find_branch_issue(&self, branch: &str) -> Result<Option<IssueFragment>, ApiError> {
+        let client = &self.client;
+        let variables = issue_vcs_branch_search_query::Variables {
+            branch_name: branch.to_string(),
+        };
+        let data = client.query(IssueVcsBranchSearchQuery, variables).await?;
+        if let Some(issue) = data.issue_vcs_branch_search {
+            return Ok(Some(issue.into()));
+        }
+        let Some(identifier) = git::identifier_from_branch(branch) else {
+            return Ok(None);
+        };
+        let variables = issue_query::Variables { id: identifier };
+        match client.query(IssueQuery, variables).await {
+            Ok(data) => Ok(Some(data.issue.into())),
+            // a branch name that only looks like it starts with an identifier
+            Err(e) if e.is_not_found() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn load_hierarchy(&self) {
+        let issue_id = {
+            let mut state = self.state.write().unwrap();
+            match &state.selected_issue {
+                Some(issue) => {
+                    let issue_id = issue.id.clone();
+                    state.hierarchy_loading_state = LoadingState::Loading;
+                    issue_id
+                }
+                None => {
+                    state.hierarchy_loading_state = LoadingState::Idle;
+                    return;
+                }
+            }
+        };
+        tokio::spawn(self.clone().fetch_hierarchy(issue_id));
+    }
+
+    async fn fetch_hierarchy(self, issue_id: String) {
+        // skip the request while the selection is still moving
+        tokio::time::sleep(HIERARCHY_DELAY).await;
+        if !self.is_selected(&issue_id) {
+            return;
+        }
+        let client = &self.client;
+        let variables = issue_hierarchy_query::Variables {
+            issue_id: issue_id.clone(),
+        };
+        let result = client.query(IssueHierarchyQuery, variables).await;
+        if !self.is_selected(&issue_id) {
+            return;
+        }
+        let mut state = self.state.write().unwrap();
+        match result {
+            Ok(data) => {
+                state.parent = data.issue.parent;
+                state.sub_issues = sub_issues(data.issue.children);
+                state.sub_issue_cursor = None;
+                state.hierarchy_loading_state = LoadingState::Loaded;
+            }
+            Err(e) => state.hierarchy_loading_state = LoadingState::Error(e.to_string()),
+        }
+    }
+
+    pub fn load_relations(&self) {
+        let issue_id = {
+            let mut state = self.state.write().unwrap();
+            match &state.selected_issue {
+                Some(issue) => {
+                    let issue_id = issue.id.clone();
+                    state.relations_loading_state = LoadingState::Loading;
+                    issue_id
+                }
+                None => {
+                    state.relations_loading_state = LoadingState::Idle;
+                    return;
+                }
+            }
+        };
+        tokio::spawn(self.clone().fetch_relations(issue_id));
+    }
+
+    async fn fetch_relations(self, issue_id: String) {
+        tokio::time::sleep(HIERARCHY_DELAY).await;
+        if !self.is_selected(&issue_id) {
+            return;
+        }
+        let client = &self.client;
+        let variables = issue_relations_query::Variables {
+            issue_id: issue_id.clone(),
+        };
+        let result = client.query(IssueRelationsQuery, variables).await;
+        if !self.is_selected(&issue_id) {
+            return;
+        }
+        let mut state = self.state.write().unwrap();
+        match result {
+            Ok(data) => {
+                state.relations = relations(data.issue);
+                state.relations_loading_state = LoadingState::Loaded;
+            }
+            Err(e) => state.relations_loading_state = LoadingState::Error(e.to_string()),
+        }
+    }
+
+    // the shown issue and its relations, for the relation picker
+    pub fn selected_issue(&self) -> Option<IssueFragment> {
+        self.state.read().unwrap().selected_issue.clone()
+    }
+
+    pub fn set_notice(&self, notice: String) {
+        self.state.write().unwrap().notice = Some(notice);
+    }
+
+    pub fn set_message(&self, message: String) {
+        self.state.write().unwrap().message = Some(message);
+    }
+
+    pub fn hold_description(&self, pending: PendingDescription) {
+        self.state.write().unwrap().pending_description = Some(pending);
+    }
+
+    pub fn has_pending_description(&self) -> bool {
+        self.state.read().unwrap().pending_description.is_some()
+    }
+
+    pub fn take_pending_description(&self) -> Option<PendingDescription> {
+        self.state.write().unwrap().pending_description.take()
+    }
+
+    // the edit isn't saved to Linear, but it's kept in a file rather than thrown away. The
+    // prompt stays up if the file can't be written.
+    fn keep_pending_description(&self) {
+        let mut state = self.state.write().unwrap();
+        let Some(pending) = &state.pending_description else {
+            return;
+        };
+        match editor::keep(&pending.description, &pending.issue.identifier) {
+            Ok(path) => {
+                state.notice = Some(format!("Your edit was kept in {}", path.display()));
+                state.pending_description = None;
+            }
+            Err(e) => state.notice = Some(format!("Couldn't keep your edit: {}", e)),
+        }
+    }
+
+    pub fn relations(&self) -> Option<(IssueFragment, Vec<Relation>)> {
+        let state = self.state.read().unwrap();
+        let issue = state.selected_issue.clone()?;
+        Some((issue, state.relations.clone()))
+    }
+
+    // sub-issues of a sub-issue are only fetched once it's expanded
+    async fn fetch_sub_issues(self, selected_id: String, issue_id: String) {
+        let client = &self.client;
+        let variables = issue_hierarchy_query::Variables {
+            issue_id: issue_id.clone(),
+        };
+        let result = client.query(IssueHierarchyQuery, variables).await;
+        if !self.is_selected(&selected_id) {
+            return;
+        }
+        let mut state = self.state.write().unwrap();
+        match result {
+            Ok(data) => {
+                if let Some(node) = find_sub_issue(&mut state.sub_issues, &issue_id) {
+                    node.children = Some(sub_issues(data.issue.children));
+                }
+            }
+            Err(e) => {
+                if let Some(node) = find_sub_issue(&mut state.sub_issues, &issue_id) {
+                    node.expanded = false;
+                }
+                state.notice = Some(e.to_string());
+            }
+        }
+    }
+
+    fn move_sub_issue_cursor(&self, forward: bool) {
+        let mut state = self.state.write().unwrap();
+        let len = state.visible_sub_issues().len();
+        if len == 0 {
+            return;
+        }
+        state.sub_issue_cursor = Some(match (state.sub_issue_cursor, forward) {
+            (None, true) => 0,
+            (None, false) => len - 1,
+            (Some(cursor), true) => (cursor + 1) % len,
+            (Some(cursor), false) => (cursor + len - 1) % len,
+        });
+    }
+
+    fn toggle_sub_issue(&self) {
+        let mut state = self.state.write().unwrap();
+        let Some(highlighted) = state.highlighted_sub_issue() else {
+            return;
+        };
+        let Some(selected_id) = state.selected_issue.as_ref().map(|issue| issue.id.clone()) else {
+            return;
+        };
+        if highlighted.progress.1 == 0 {
+            return;
+        }
+        let Some(node) = find_sub_issue(&mut state.sub_issues, &highlighted.issue.id) else {
+            return;
+        };
+        node.expanded = !node.expanded;
+        if node.expanded && node.children.is_none() {
+            tokio::spawn(
+                self.clone()
+                    .fetch_sub_issues(selected_id, highlighted.issue.id),
+            );
+        }
+    }
+
+    pub fn toggle_comments(&mut self) {
+        let show_comments = {
+            let mut state = self.state.write().unwrap();
+            state.show_comments = !state.show_comments;
+            state.show_comments
+        };
+        if show_comments {
+            self.load_comments();
+        } else {
+            self.replying = false;
+        }
+    }
+
+    fn load_comments(&self) {
+        let issue_id = {
+            let mut state = self.state.write().unwrap();
+            match &state.selected_issue {
+                Some(issue) => {
+                    let issue_id = issue.id.clone();
+                    state.comments_loading_state = LoadingState::Loading;
+                    issue_id
+                }
+                None => return,
+            }
+        };
+        tokio::spawn(self.clone().fetch_comments(issue_id));
+    }
+
+    // results for an issue that is no longer selected are dropped
+    fn is_selected(&self, issue_id: &str) -> bool {
+        self.state
+            .read()
+            .unwrap()
+            .selected_issue
+            .as_ref()
+            .is_some_and(|issue| issue.id == issue_id)
+    }
+
+    async fn fetch_comments(self, issue_id: String) {
+        let client = &self.client;
+        let variables = issue_comments_query::Variables {
+            issue_id: issue_id.clone(),
+        };
+        let result = client.query(IssueCommentsQuery, variables).await;
+        if !self.is_selected(&issue_id) {
+            return;
+        }
+        let mut state = self.state.write().unwrap();
+        match result {
+            Ok(data) => {
+                let mut comments: Vec<CommentFragment> = data
+                    .issue
+                    .comments
+                    .nodes
+                    .into_iter()
+                    .map(|comment| comment.into())
+                    .collect();
+                comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+                state.comments = comments;
+                state.comments_loading_state = LoadingState::Loaded;
+            }
+            Err(e) => {
+                state.comments_loading_state = LoadingState::Error(e.to_string());
+            }
+        }
+    }
+
+    fn start_reply(&mut self) {
+        let state = self.state.read().unwrap();
+        if state.show_comments && state.selected_issue.is_some() {
+            self.replying = true;
+            self.reply_input.reset();
+        }
+    }
+
+    fn send_reply(&mut self) {
+        let body = self.reply_input.value().trim().to_string();
+        self.replying = false;
+        let issue_id = match &self.state.read().unwrap().selected_issue {
+            Some(issue) if !body.is_empty() => issue.id.clone(),
+            _ => return,
+        };
+        tokio::spawn(self.clone().post_comment(issue_id, body));
+    }
+
+    async fn post_comment(self, issue_id: String, body: String) {
+        let client = &self.client;
+        let variables = comment_create_mutation::Variables {
+            input: comment_create_mutation::CommentCreateInput {
+                issue_id: Some(issue_id.clone()),
+                body: Some(body),
+                ..Default::default()
+            },
+        };
+        let result = client.query(CommentCreateMutation, variables).await;
+        if !self.is_selected(&issue_id) {
+            return;
+        }
+        let mut state = self.state.write().unwrap();
+        match result {
+            Ok(data) => {
+                state.comments.push(data.comment_create.comment.into());
+                state.reply_error = None;
+            }
+            Err(e) => state.reply_error = Some(format!("Couldn't post reply: {}", e)),
+        }
+    }
+
+    // swap in a newer copy of the shown issue, keeping the scroll position
+    pub fn refresh_issue(&self, issue: &IssueFragment) {
+        let mut state = self.state.write().unwrap();
+        if let Some(selected) = &state.selected_issue
+            && selected.id == issue.id
+        {
+            state.selected_issue = Some(issue.clone());
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> LtEvent<'_> {
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            if self.replying {
                 match key.code {
-                    KeyCode::Down => {
-                        self.scroll = self.scroll.saturating_add(1);
-                        self.scroll_state.next();
-                    }
-                    KeyCode::Up => {
-                        self.scroll = self.scroll.saturating_sub(1);
-                        self.scroll_state.prev();
+                    KeyCode::Esc => self.replying = false,
+                    KeyCode::Enter => self.send_reply(),
+                    _ => {
+                        self.reply_input.handle_event(event);
                     }
+                }
+                return LtEvent::None;
+            }
+            if self.has_pending_description() {
+                match key.code {
+                    KeyCode::Enter => return LtEvent::OverwriteDescription,
+                    KeyCode::Esc => self.keep_pending_description(),
                     _ => {}
                 }
+                return LtEvent::None;
+            }
+            match self.keymap.action(key) {
+                Some(Action::ToggleComments) => self.toggle_comments(),
+                Some(Action::Reply) => self.start_reply(),
+                Some(Action::EditDescription)
+                    if self.state.read().unwrap().selected_issue.is_some() =>
+                {
+                    return LtEvent::EditDescription;
+                }
+                Some(Action::ScrollDown) => {
+                    self.scroll = self.scroll.saturating_add(1);
+                    self.scroll_state.next();
+                }
+                Some(Action::ScrollUp) => {
+                    self.scroll = self.scroll.saturating_sub(1);
+                    self.scroll_state.prev();
+                }
+                Some(Action::Parent) => {
+                    let parent = self.state.read().unwrap().parent.clone();
+                    if let Some(parent) = parent {
+                        self.open_issue(parent);
+                    }
+                }
+                Some(Action::NextSubIssue) => self.move_sub_issue_cursor(true),
+                Some(Action::PrevSubIssue) => self.move_sub_issue_cursor(false),
+                Some(Action::OpenSubIssue) => {
+                    let highlighted = self.state.read().unwrap().highlighted_sub_issue();
+                    if let Some(sub_issue) = highlighted {
+                        self.open_issue(sub_issue.issue);
+                    }
+                }
+                Some(Action::ExpandSubIssue) => self.toggle_sub_issue(),
+                _ => {}
             }
         }
         LtEvent::None
     }
 }
 
+const NBSP: &str = "\u{a0}";
+
 const DICT_HEADER: Style = Style::new();
 
-fn header(text: &str) -> Line {
+fn header(text: &str) -> Line<'_> {
     Line::from(Span::from(text.to_owned() + ":\n")).style(DICT_HEADER)
 }
 
+fn sub_issue_lines(state: &SelectedIssueWidgetState, keymap: &Keymap) -> Vec<Line<'static>> {
+    let mut lines = vec![];
+    if let Some(message) = &state.message {
+        lines.push(Line::from(""));
+        lines.push(Line::from(message.clone().dark_gray()));
+    }
+    if let Some(notice) = &state.notice {
+        lines.push(Line::from(""));
+        lines.push(Line::from(notice.clone().red()));
+    }
+    if let Some(issue) = &state.opening {
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!("Opening {}…", issue.identifier)));
+    }
+    if let LoadingState::Error(e) = &state.hierarchy_loading_state {
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!("Couldn't load sub-issues: {}", e).red()));
+    }
+    if state.sub_issues.is_empty() {
+        return lines;
+    }
+
+    let closed = state
+        .sub_issues
+        .iter()
+        .filter(|sub_issue| is_closed(&sub_issue.issue.state.type_))
+        .count();
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::from("Sub-issues").bold(),
+        Span::from(format!(" {}/{}", closed, state.sub_issues.len())).dark_gray(),
+        Span::from(" ─ "),
+        Span::from(format!(
+            "<{}/{}> ",
+            keymap.label(Action::NextSubIssue),
+            keymap.label(Action::PrevSubIssue)
+        ))
+        .blue(),
+        Span::from("to pick ").dark_gray(),
+        Span::from(format!("<{}> ", keymap.label(Action::OpenSubIssue))).blue(),
+        Span::from("to open ").dark_gray(),
+        Span::from(format!("<{}> ", keymap.label(Action::ExpandSubIssue))).blue(),
+        Span::from("to expand").dark_gray(),
+    ]));
+    // tests can't see the highlighting, and a non-breaking space keeps the wrapping from
+    // trimming the indentation of the tree
+    let cursor_symbol = if cfg!(test) { ">" } else { NBSP };
+    for (index, (depth, sub_issue)) in state.visible_sub_issues().into_iter().enumerate() {
+        let highlighted = state.sub_issue_cursor == Some(index);
+        let color = Color::from_str(&sub_issue.issue.state.color).unwrap_or(Color::White);
+        let marker = match (sub_issue.progress.1, sub_issue.expanded) {
+            (0, _) => "  ",
+            (_, true) => "▾ ",
+            (_, false) => "▸ ",
+        };
+        let mut spans = vec![
+            Span::from(if highlighted { cursor_symbol } else { NBSP }),
+            Span::from("  ".repeat(depth)),
+            Span::from(marker),
+            Span::from(iconmap::state_to_nf(&sub_issue.issue.state.type_)).fg(color),
+            Span::from(sub_issue.issue.identifier.clone()).blue().bold(),
+            Span::from(" "),
+            Span::from(sub_issue.issue.title.clone()),
+        ];
+        if sub_issue.progress.1 > 0 {
+            spans.push(
+                Span::from(format!(
+                    " {}/{}",
+                    sub_issue.progress.0, sub_issue.progress.1
+                ))
+                .dark_gray(),
+            );
+        }
+        let mut line = Line::from(spans);
+        if highlighted {
+            line = line.style(Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        }
+        lines.push(line);
+        if sub_issue.expanded && sub_issue.children.is_none() {
+            lines.push(
+                Line::from(format!("{}{}    Loading…", NBSP, "  ".repeat(depth))).dark_gray(),
+            );
+        }
+    }
+    lines
+}
+
+fn relation_lines(state: &SelectedIssueWidgetState, keymap: &Keymap) -> Vec<Line<'static>> {
+    let mut lines = vec![];
+    if let LoadingState::Error(e) = &state.relations_loading_state {
+        lines.push(Line::from(format!("Couldn't load relations: {}", e).red()));
+        lines.push(Line::from(""));
+    }
+    if state.relations.is_empty() {
+        return lines;
+    }
+    for kind in RelationKind::ALL {
+        let related: Vec<&Relation> = state
+            .relations
+            .iter()
+            .filter(|relation| relation.kind == kind)
+            .collect();
+        if related.is_empty() {
+            continue;
+        }
+        lines.push(header(kind.label()));
+        for relation in related {
+            let issue = &relation.issue;
+            let mut identifier = issue.identifier.clone().bold();
+            // an open blocker is what makes the issue blocked
+            if kind == RelationKind::BlockedBy && !is_closed(&issue.state.type_) {
+                identifier = identifier.red();
+            }
+            lines.push(Line::from(vec![
+                iconmap::state_to_nf(&issue.state.type_)
+                    .fg(Color::from_str(&issue.state.color).unwrap_or(Color::White)),
+                identifier,
+                " ".into(),
+                issue.title.clone().into(),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+    lines.insert(
+        lines.len() - 1,
+        Line::from(vec![
+            Span::from(format!("<{}> ", keymap.label(Action::Relations))).blue(),
+            Span::from("to edit").dark_gray(),
+        ]),
+    );
+    lines
+}
+
+fn comment_lines<'a>(state: &'a SelectedIssueWidgetState, keymap: &Keymap) -> Vec<Line<'a>> {
+    let toggle_hint = format!("<{}> ", keymap.label(Action::ToggleComments));
+    let mut lines = vec![Line::from("")];
+    if !state.show_comments {
+        lines.push(Line::from(vec![
+            Span::from(toggle_hint).blue(),
+            Span::from("to show comments").dark_gray(),
+        ]));
+        return lines;
+    }
+
+    let count = match state.comments_loading_state {
+        LoadingState::Loaded => format!(" ({})", state.comments.len()),
+        _ => String::new(),
+    };
+    lines.push(Line::from(vec![
+        Span::from(format!("Comments{}", count)).bold(),
+        Span::from(" ─ "),
+        Span::from(format!("<{}> ", keymap.label(Action::Reply))).blue(),
+        Span::from("to reply ").dark_gray(),
+        Span::from(toggle_hint).blue(),
+        Span::from("to hide").dark_gray(),
+    ]));
+    if let Some(error) = &state.reply_error {
+        lines.push(Line::from(error.clone().red()));
+    }
+    lines.push(Line::from(""));
+
+    match &state.comments_loading_state {
+        LoadingState::Error(e) => lines.push(Line::from(e.clone().red())),
+        LoadingState::Loaded if state.comments.is_empty() => {
+            lines.push(Line::from("No comments yet".dark_gray()));
+        }
+        LoadingState::Loaded => {
+            for comment in &state.comments {
+                let author = match &comment.user {
+                    Some(user) if user.is_me => String::from("You"),
+                    Some(user) => user.display_name.clone(),
+                    None => String::from("Unknown"),
+                };
+                let created_at = DateTime::parse_from_rfc3339(&comment.created_at)
+                    .map(|date| date.format("%m/%d/%Y %H:%M").to_string())
+                    .unwrap_or_default();
+                lines.push(Line::from(vec![
+                    Span::from(author).blue().bold(),
+                    Span::from(" · "),
+                    Span::from(created_at).dark_gray(),
+                ]));
+                lines.extend(tui_markdown::from_str(&comment.body).lines);
+                lines.push(Line::from(""));
+            }
+        }
+        _ => lines.push(Line::from("Loading comments…")),
+    }
+    lines
+}
+
 impl Widget for &SelectedIssueWidget {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let state = self.state.read().unwrap();
@@ -86,11 +863,13 @@ impl Widget for &SelectedIssueWidget {
                 Some(issue) => {
                     let identifier = issue.identifier.clone().blue().bold();
                     let title_text = Line::from(issue.title.clone()).centered();
-                    let description = if issue.description.is_some() {
-                        tui_markdown::from_str(issue.description.as_ref().unwrap())
+                    let mut description = if let Some(description) = &issue.description {
+                        tui_markdown::from_str(description)
                     } else {
                         tui_markdown::from_str("#### No description")
                     };
+                    description.extend(sub_issue_lines(&state, &self.keymap));
+                    description.extend(comment_lines(&state, &self.keymap));
                     let created_at = DateTime::parse_from_rfc3339(&issue.created_at.clone())
                         .unwrap()
                         .format("%m/%d/%Y")
@@ -98,6 +877,24 @@ impl Widget for &SelectedIssueWidget {
 
                     let mut sidebar_items = vec![];
 
+                    if let Some(parent) = &state.parent {
+                        sidebar_items.push(header("Parent"));
+                        sidebar_items.push(Line::from(vec![
+                            iconmap::state_to_nf(&parent.state.type_)
+                                .fg(Color::from_str(&parent.state.color).unwrap_or(Color::White)),
+                            parent.identifier.clone().bold(),
+                            " ".into(),
+                            parent.title.clone().into(),
+                        ]));
+                        sidebar_items.push(Line::from(vec![
+                            Span::from(format!("<{}> ", self.keymap.label(Action::Parent))).blue(),
+                            Span::from("to open").dark_gray(),
+                        ]));
+                        sidebar_items.push(Line::from(""));
+                    }
+
+                    sidebar_items.extend(relation_lines(&state, &self.keymap));
+
                     if !issue.priority_label.is_empty() {
                         sidebar_items.push(header("Priority"));
                         let priority_icon = iconmap::p_to_nf(issue.priority);
@@ -227,12 +1024,15 @@ impl Widget for &SelectedIssueWidget {
             .border_style(DICT_HEADER)
             .title_bottom(Line::from(vec![
                 Span::from("──"),
-                Span::from(" <y> ").blue(),
+                Span::from(format!(" <{}> ", self.keymap.label(Action::CopyBranch))).blue(),
                 Span::from("to yank git branch "),
             ]))
             .title_bottom(Line::from(vec![
-                Span::from(" <o> ").blue(),
-                Span::from("to open in Linear"),
+                Span::from(format!(" <{}> ", self.keymap.label(Action::OpenUrl))).blue(),
+                Span::from("to open in Linear "),
+                Span::from("─"),
+                Span::from(format!(" <{}> ", self.keymap.label(Action::ChangeState))).blue(),
+                Span::from("to change status"),
             ]))
             .title(identifier)
             .title(title_text);
@@ -240,7 +1040,12 @@ impl Widget for &SelectedIssueWidget {
         block = if scroll_enabled {
             block.title_bottom(
                 Line::from(vec![
-                    Span::from(" <↑/↓> ").blue(),
+                    Span::from(format!(
+                        " <{}/{}> ",
+                        self.keymap.label(Action::ScrollUp),
+                        self.keymap.label(Action::ScrollDown)
+                    ))
+                    .blue(),
                     Span::from("to scroll ──────"),
                 ])
                 .right_aligned(),
@@ -259,7 +1064,7 @@ impl Widget for &SelectedIssueWidget {
             .border_set(collapsed_top_and_left_border_set)
             .title_bottom(Line::from(vec![
                 Span::from("──"),
-                Span::from(" <q> ").blue(),
+                Span::from(format!(" <{}> ", self.keymap.label(Action::Quit))).blue(),
                 Span::from("to quit "),
             ]))
             .title(created_at_title);
@@ -268,6 +1073,47 @@ impl Widget for &SelectedIssueWidget {
             .block(sidebar_block)
             .wrap(Wrap { trim: true });
 
+        let main = if self.replying {
+            let [main, reply_area] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(3)]).areas(main);
+            let reply_block = Block::bordered().title(Line::from(vec![
+                Span::from(" Reply "),
+                Span::from("─"),
+                Span::from(" <enter> ").blue(),
+                Span::from("to send "),
+                Span::from("─"),
+                Span::from(" <esc> ").blue(),
+                Span::from("to cancel "),
+            ]));
+            Paragraph::new(self.reply_input.value().to_owned() + "|")
+                .style(Color::Yellow)
+                .block(reply_block)
+                .render(reply_area, buf);
+            main
+        } else if let Some(pending) = &state.pending_description {
+            let [main, prompt_area] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(3)]).areas(main);
+            let prompt_block = Block::bordered().title(Line::from(vec![
+                Span::from(" Save description "),
+                Span::from("─"),
+                Span::from(" <enter> ").blue(),
+                Span::from("to overwrite "),
+                Span::from("─"),
+                Span::from(" <esc> ").blue(),
+                Span::from("to save it to a file "),
+            ]));
+            Paragraph::new(format!(
+                "{} was changed in Linear while you were editing it",
+                pending.issue.identifier
+            ))
+            .style(Color::Red)
+            .block(prompt_block)
+            .render(prompt_area, buf);
+            main
+        } else {
+            main
+        };
+
         //Clear.render(main, buf);
         p.render(main, buf);
         sidebar_p.render(sidebar, buf);
@@ -278,16 +1124,23 @@ impl Widget for &SelectedIssueWidget {
 #[cfg(test)]
 pub(crate) mod tests {
     use crate::{
-        IssueFragment, IssueFragmentAssignee, IssueFragmentCreator, IssueFragmentProject,
-        IssueFragmentState,
+        CommentFragment, CommentFragmentUser, IssueFragment, IssueFragmentAssignee,
+        IssueFragmentCreator, IssueFragmentProject, IssueFragmentState, LoadingState,
     };
+    use crossterm::event::KeyCode;
     use insta::assert_snapshot;
     use ratatui::{Terminal, backend::TestBackend};
 
-    use crate::widgets::SelectedIssueWidget;
+    use crate::{
+        LtEvent,
+        stub_server::StubServer,
+        widgets::{PendingDescription, SelectedIssueWidget, create_key_event},
+    };
+    use std::sync::Arc;
 
     pub fn make_issue(title: &str, identifier: &str) -> IssueFragment {
         IssueFragment {
+            id: format!("{}-id", identifier),
             priority: 1.0,
             priority_label: "Urgent".into(),
             branch_name: "test-1-branch-name".into(),
@@ -308,6 +1161,7 @@ pub(crate) mod tests {
                 display_name: "Assignee Display Name".into(),
             }),
             state: IssueFragmentState {
+                id: "backlog-id".into(),
                 name: "Backlogged".into(),
                 color: "#0FA0FA".into(),
                 type_: "backlog".into(),
@@ -327,8 +1181,54 @@ pub(crate) mod tests {
         assert_snapshot!(terminal.backend());
     }
 
-    #[test]
-    fn test_basic_issue() {
+    #[tokio::test]
+    async fn test_comments() {
+        let mut app = SelectedIssueWidget::default();
+        app.set_selected_issue(Some(make_issue("Testing Ticket", "TEST-1")));
+        {
+            let mut state = app.state.write().unwrap();
+            state.show_comments = true;
+            state.comments_loading_state = LoadingState::Loaded;
+            state.comments = vec![
+                CommentFragment {
+                    id: "comment-1".into(),
+                    body: "Looks like a **regression**".into(),
+                    created_at: "2025-05-11T10:00:00.000Z".into(),
+                    user: Some(CommentFragmentUser {
+                        display_name: "Assignee Display Name".into(),
+                        is_me: false,
+                    }),
+                },
+                CommentFragment {
+                    id: "comment-2".into(),
+                    body: "On it".into(),
+                    created_at: "2025-05-11T11:30:00.000Z".into(),
+                    user: Some(CommentFragmentUser {
+                        display_name: "Creator Display Name".into(),
+                        is_me: true,
+                    }),
+                },
+            ];
+        }
+
+        app.handle_event(&create_key_event(KeyCode::Char('r')));
+        assert!(app.replying);
+        for c in "Thanks".chars() {
+            app.handle_event(&create_key_event(KeyCode::Char(c)));
+        }
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        app.handle_event(&create_key_event(KeyCode::Esc));
+        assert!(!app.replying);
+    }
+
+    #[tokio::test]
+    async fn test_basic_issue() {
         let mut app = SelectedIssueWidget::default();
         let issue = make_issue("Testing Ticket", "TEST-1");
 
@@ -340,4 +1240,170 @@ pub(crate) mod tests {
             .unwrap();
         assert_snapshot!(terminal.backend());
     }
+
+    #[tokio::test]
+    async fn test_sub_issues() {
+        let server = StubServer::start();
+        let mut app = SelectedIssueWidget::new(server.client(), Arc::default());
+        let mut issue = make_issue("Settings page", "LT-4");
+        issue.id = String::from("issue-4");
+        app.state.write().unwrap().selected_issue = Some(issue);
+        app.clone().fetch_hierarchy(String::from("issue-4")).await;
+
+        app.handle_event(&create_key_event(KeyCode::Char(']')));
+        let mut terminal = Terminal::new(TestBackend::new(100, 24)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        // expanding fetches the sub-issue's own sub-issues
+        server.respond_with(
+            "IssueHierarchyQuery",
+            r##"{"data": {"issue": {"parent": null, "children": {"nodes": [{"id": "issue-7",
+                "identifier": "LT-7", "title": "Backfill", "state": {"name": "Todo",
+                "color": "#e2e2e2", "type": "unstarted"}, "children": {"nodes": []}}]}}}}"##,
+        );
+        app.handle_event(&create_key_event(KeyCode::Char(' ')));
+        app.clone()
+            .fetch_sub_issues(String::from("issue-4"), String::from("issue-5"))
+            .await;
+        let rows: Vec<(usize, String)> = app
+            .state
+            .read()
+            .unwrap()
+            .visible_sub_issues()
+            .into_iter()
+            .map(|(depth, sub_issue)| (depth, sub_issue.issue.identifier))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                (0, String::from("LT-5")),
+                (1, String::from("LT-7")),
+                (0, String::from("LT-6"))
+            ]
+        );
+
+        // opening a sub-issue loads it even though it isn't in any list
+        app.handle_event(&create_key_event(KeyCode::Char('g')));
+        app.clone().fetch_issue(String::from("issue-5")).await;
+        let state = app.state.read().unwrap();
+        assert_eq!(state.selected_issue.as_ref().unwrap().identifier, "LT-5");
+        assert!(state.opening.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_relations() {
+        let server = StubServer::start();
+        let app = SelectedIssueWidget::new(server.client(), Arc::default());
+        let mut issue = make_issue("Settings page", "LT-4");
+        issue.id = String::from("issue-4");
+        app.state.write().unwrap().selected_issue = Some(issue);
+        app.clone().fetch_relations(String::from("issue-4")).await;
+
+        let (_, relations) = app.relations().unwrap();
+        let identifiers: Vec<&str> = relations
+            .iter()
+            .map(|relation| relation.issue.identifier.as_str())
+            .collect();
+        assert_eq!(identifiers, ["LT-5", "LT-7", "LT-8"]);
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_pending_description() {
+        let mut app = SelectedIssueWidget::default();
+        let issue = make_issue("Testing Ticket", "TEST-1");
+        app.state.write().unwrap().selected_issue = Some(issue.clone());
+        assert_eq!(
+            app.handle_event(&create_key_event(KeyCode::Char('e'))),
+            LtEvent::EditDescription
+        );
+
+        app.hold_description(PendingDescription {
+            issue,
+            description: String::from("Edited"),
+        });
+        let mut terminal = Terminal::new(TestBackend::new(100, 12)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        // other keys wait for an answer
+        assert_eq!(
+            app.handle_event(&create_key_event(KeyCode::Char('t'))),
+            LtEvent::None
+        );
+        assert_eq!(
+            app.handle_event(&create_key_event(KeyCode::Enter)),
+            LtEvent::OverwriteDescription
+        );
+        app.handle_event(&create_key_event(KeyCode::Esc));
+        assert!(!app.has_pending_description());
+        let notice = app.state.read().unwrap().notice.clone().unwrap();
+        let path = notice.strip_prefix("Your edit was kept in ").unwrap();
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "Edited\n");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_branch_issue() {
+        let server = StubServer::start();
+        let app = SelectedIssueWidget::new(server.client(), Arc::default());
+        app.clone()
+            .fetch_branch_issue(String::from("lt-5-write-migration"))
+            .await;
+        assert_eq!(app.selected_issue().unwrap().identifier, "LT-5");
+
+        // branches Linear doesn't know about are looked up by the identifier they start with
+        let app = SelectedIssueWidget::new(server.client(), Arc::default());
+        server.respond_with(
+            "IssueVcsBranchSearchQuery",
+            r#"{"data": {"issueVcsBranchSearch": null}}"#,
+        );
+        app.clone()
+            .fetch_branch_issue(String::from("me/lt-5-migration"))
+            .await;
+        assert_eq!(app.selected_issue().unwrap().identifier, "LT-5");
+
+        // an issue the user picked meanwhile stays
+        let app = SelectedIssueWidget::new(server.client(), Arc::default());
+        app.state.write().unwrap().selected_issue = Some(make_issue("Testing Ticket", "TEST-1"));
+        app.clone()
+            .fetch_branch_issue(String::from("lt-5-write-migration"))
+            .await;
+        assert_eq!(app.selected_issue().unwrap().identifier, "TEST-1");
+
+        // a failed lookup is shown, an unknown identifier isn't
+        let app = SelectedIssueWidget::new(server.client(), Arc::default());
+        server.respond_with(
+            "IssueQuery",
+            r#"{"errors": [
+                {"message": "Entity not found: Issue", "extensions": {"code": "INPUT_ERROR"}}
+            ]}"#,
+        );
+        app.clone()
+            .fetch_branch_issue(String::from("release-2-notes"))
+            .await;
+        assert!(app.selected_issue().is_none());
+        assert_eq!(app.state.read().unwrap().notice, None);
+        server.respond_with(
+            "IssueVcsBranchSearchQuery",
+            r#"{"errors": [{"message": "Internal error"}]}"#,
+        );
+        app.clone()
+            .fetch_branch_issue(String::from("lt-5-write-migration"))
+            .await;
+        assert_eq!(
+            app.state.read().unwrap().notice.as_deref(),
+            Some("Couldn't look up the issue of lt-5-write-migration: Internal error")
+        );
+    }
 }
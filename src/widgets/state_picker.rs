@@ -0,0 +1,361 @@
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Clear, List, ListItem, ListState, Paragraph, StatefulWidget, Widget},
+};
+
+use crate::{
+    IssueFragment, LoadingState, LtEvent,
+    api::LinearClient,
+    config::{Action, Keymap},
+    iconmap,
+    queries::{
+        WorkflowStatesQuery,
+        workflow_states_query::{self, WorkflowStateFragment},
+    },
+    widgets::popup_area,
+};
+
+// Linear shows workflow states grouped in this order
+pub(crate) const STATE_TYPE_ORDER: [&str; 6] = [
+    "triage",
+    "backlog",
+    "unstarted",
+    "started",
+    "completed",
+    "cancelled",
+];
+
+#[derive(Debug, Default)]
+struct StatePickerWidgetState {
+    loading_state: LoadingState,
+    list_state: ListState,
+    issue: Option<IssueFragment>,
+    states: Vec<WorkflowStateFragment>,
+    // shown above the states when the picker was opened for something the user just did
+    message: Option<String>,
+    // the picker starts on the first state of this type instead of the current one
+    preferred_type: Option<&'static str>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StatePickerWidget {
+    state: Arc<RwLock<StatePickerWidgetState>>,
+    client: Arc<LinearClient>,
+    keymap: Arc<Keymap>,
+    pub visible: bool,
+}
+
+impl StatePickerWidget {
+    pub fn new(client: Arc<LinearClient>, keymap: Arc<Keymap>) -> Self {
+        Self {
+            client,
+            keymap,
+            ..Default::default()
+        }
+    }
+
+    pub fn open(&mut self, issue: IssueFragment) {
+        self.show(issue, None, None);
+    }
+
+    // offers to move an issue that work was just started on to the team's started state
+    pub fn offer_start(&mut self, issue: IssueFragment, message: String) {
+        self.show(issue, Some(message), Some("started"));
+    }
+
+    fn show(
+        &mut self,
+        issue: IssueFragment,
+        message: Option<String>,
+        preferred_type: Option<&'static str>,
+    ) {
+        self.visible = true;
+        {
+            let mut state = self.state.write().unwrap();
+            state.loading_state = LoadingState::Loading;
+            state.list_state.select(None);
+            state.states.clear();
+            state.issue = Some(issue.clone());
+            state.message = message;
+            state.preferred_type = preferred_type;
+        }
+        tokio::spawn(self.clone().fetch(issue.team.id));
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+    }
+
+    async fn fetch(self, team_id: String) {
+        let client = &self.client;
+        let variables = workflow_states_query::Variables { team_id };
+        match client.query(WorkflowStatesQuery, variables).await {
+            Ok(data) => self.set_states(data.team.states.nodes),
+            Err(e) => {
+                self.state.write().unwrap().loading_state = LoadingState::Error(e.to_string());
+            }
+        }
+    }
+
+    fn set_states(&self, mut states: Vec<WorkflowStateFragment>) {
+        states.sort_by(|a, b| {
+            let type_index = |t: &str| STATE_TYPE_ORDER.iter().position(|o| *o == t);
+            type_index(&a.type_)
+                .cmp(&type_index(&b.type_))
+                .then(a.position.total_cmp(&b.position))
+        });
+        let mut state = self.state.write().unwrap();
+        // start on the preferred or the issue's current state
+        let current = state.issue.as_ref().map(|issue| issue.state.id.clone());
+        let preferred = state
+            .preferred_type
+            .and_then(|type_| states.iter().position(|s| s.type_ == type_));
+        let selected = preferred
+            .or_else(|| states.iter().position(|s| Some(&s.id) == current.as_ref()))
+            .or(if states.is_empty() { None } else { Some(0) });
+        state.list_state.select(selected);
+        state.states = states;
+        state.loading_state = LoadingState::Loaded;
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> LtEvent<'_> {
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            match (self.keymap.action(key), key.code) {
+                (Some(Action::Quit), _) | (_, KeyCode::Esc) => self.close(),
+                (Some(Action::Down), _) | (_, KeyCode::Down) => {
+                    let mut state = self.state.write().unwrap();
+                    if !state.states.is_empty() {
+                        let next = state
+                            .list_state
+                            .selected()
+                            .map_or(0, |i| (i + 1) % state.states.len());
+                        state.list_state.select(Some(next));
+                    }
+                }
+                (Some(Action::Up), _) | (_, KeyCode::Up) => {
+                    let mut state = self.state.write().unwrap();
+                    if !state.states.is_empty() {
+                        let len = state.states.len();
+                        let prev = state
+                            .list_state
+                            .selected()
+                            .map_or(len - 1, |i| (i + len - 1) % len);
+                        state.list_state.select(Some(prev));
+                    }
+                }
+                (_, KeyCode::Enter) => {
+                    let state = self.state.read().unwrap();
+                    let picked = state
+                        .list_state
+                        .selected()
+                        .and_then(|index| state.states.get(index).cloned());
+                    if let (Some(picked), Some(issue)) = (picked, state.issue.clone()) {
+                        drop(state);
+                        self.close();
+                        if picked.id != issue.state.id {
+                            return LtEvent::SetIssueState(issue.id, picked);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        LtEvent::None
+    }
+}
+
+impl Widget for &StatePickerWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if !self.visible {
+            return;
+        }
+        let area = popup_area(area, 50, 50);
+        Clear.render(area, buf);
+
+        let mut state = self.state.write().unwrap();
+        let identifier = state
+            .issue
+            .as_ref()
+            .map_or(String::new(), |issue| issue.identifier.clone());
+        let title = if state.message.is_some() {
+            "Start work? "
+        } else {
+            "Change status "
+        };
+        let block = Block::bordered()
+            .title(Line::from(vec![
+                Span::from(format!(" {} ", identifier)).blue().bold(),
+                Span::from(title),
+            ]))
+            .title_bottom(Line::from(vec![
+                Span::from(" <enter> ").blue(),
+                Span::from("to set "),
+                Span::from("─"),
+                Span::from(" <esc> ").blue(),
+                Span::from("to cancel "),
+            ]));
+
+        match &state.loading_state {
+            LoadingState::Loaded => {}
+            LoadingState::Error(e) => {
+                Paragraph::new(e.clone().red().bold())
+                    .block(block)
+                    .render(area, buf);
+                return;
+            }
+            _ => {
+                Paragraph::new("Loading…").block(block).render(area, buf);
+                return;
+            }
+        }
+
+        let current = state
+            .issue
+            .as_ref()
+            .map_or(String::new(), |issue| issue.state.id.clone());
+        let rows: Vec<ListItem> = state
+            .states
+            .iter()
+            .map(|workflow_state| {
+                let color = Color::from_str(&workflow_state.color).unwrap_or(Color::White);
+                let mut line = Line::from(vec![
+                    Span::from(iconmap::state_to_nf(&workflow_state.type_)).fg(color),
+                    Span::from(" "),
+                    Span::from(workflow_state.name.clone()),
+                ]);
+                if workflow_state.id == current {
+                    line.push_span(Span::from("  (current)").dark_gray());
+                }
+                ListItem::new(line)
+            })
+            .collect();
+
+        // tests can't see the highlighting
+        let highlight_symbol = if cfg!(test) { ">" } else { "" };
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let list_area = match &state.message {
+            Some(message) => {
+                let [message_area, list_area] =
+                    Layout::vertical([Constraint::Length(2), Constraint::Min(0)]).areas(inner);
+                Paragraph::new(message.clone().dark_gray()).render(message_area, buf);
+                list_area
+            }
+            None => inner,
+        };
+        let list = List::new(rows)
+            .highlight_style(Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+            .highlight_symbol(highlight_symbol);
+        StatefulWidget::render(list, list_area, buf, &mut state.list_state);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crossterm::event::KeyCode;
+    use insta::assert_snapshot;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use crate::{
+        LtEvent,
+        queries::workflow_states_query::WorkflowStateFragment,
+        widgets::{create_key_event, selected_issue::tests::make_issue},
+    };
+
+    use super::StatePickerWidget;
+
+    pub(crate) fn make_state(
+        id: &str,
+        name: &str,
+        type_: &str,
+        position: f64,
+    ) -> WorkflowStateFragment {
+        WorkflowStateFragment {
+            id: id.into(),
+            name: name.into(),
+            color: "#0FA0FA".into(),
+            type_: type_.into(),
+            position,
+        }
+    }
+
+    #[test]
+    fn test_pick_state() {
+        let mut app = StatePickerWidget {
+            visible: true,
+            ..Default::default()
+        };
+        app.state.write().unwrap().issue = Some(make_issue("Testing Ticket", "TEST-1"));
+        app.set_states(vec![
+            make_state("done-id", "Done", "completed", 0.0),
+            make_state("todo-id", "Todo", "unstarted", 0.0),
+            make_state("backlog-id", "Backlog", "backlog", 0.0),
+            make_state("review-id", "In Review", "started", 2.0),
+            make_state("progress-id", "In Progress", "started", 1.0),
+        ]);
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        // picking the current state is a no-op
+        let ev = app.handle_event(&create_key_event(KeyCode::Enter));
+        assert_eq!(ev, LtEvent::None);
+        assert!(!app.visible);
+
+        app.visible = true;
+        app.handle_event(&create_key_event(KeyCode::Char('j')));
+        app.handle_event(&create_key_event(KeyCode::Char('j')));
+        let ev = app.handle_event(&create_key_event(KeyCode::Enter));
+        match ev {
+            LtEvent::SetIssueState(issue_id, picked) => {
+                assert_eq!(issue_id, "TEST-1-id");
+                assert_eq!(picked.id, "progress-id");
+            }
+            _ => panic!("expected SetIssueState, got {:?}", ev),
+        }
+    }
+
+    #[test]
+    fn test_offer_start() {
+        let mut app = StatePickerWidget {
+            visible: true,
+            ..Default::default()
+        };
+        {
+            let mut state = app.state.write().unwrap();
+            state.issue = Some(make_issue("Testing Ticket", "TEST-1"));
+            state.message = Some(String::from("Created and checked out test-1-branch-name"));
+            state.preferred_type = Some("started");
+        }
+        app.set_states(vec![
+            make_state("backlog-id", "Backlog", "backlog", 0.0),
+            make_state("review-id", "In Review", "started", 2.0),
+            make_state("progress-id", "In Progress", "started", 1.0),
+        ]);
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        // the first started state is picked unless the user moves
+        match app.handle_event(&create_key_event(KeyCode::Enter)) {
+            LtEvent::SetIssueState(_, picked) => assert_eq!(picked.id, "progress-id"),
+            ev => panic!("expected SetIssueState, got {:?}", ev),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crossterm::event::{Event, KeyCode, KeyEventKind};
+use crossterm::event::{Event, KeyEventKind};
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Layout};
 use ratatui::style::Color;
@@ -12,18 +12,30 @@ use std::str::FromStr;
 use std::sync::{Arc, RwLock};
 
 use crate::api::LinearClient;
+use crate::cache::{self, CachedViews};
+use crate::config::{Action, Keymap};
 use crate::queries::{CustomViewsQuery, custom_views_query};
 use crate::{TabChangeEvent, iconmap};
 
 #[derive(Debug, Clone)]
 pub struct TabWidget {
     state: Arc<RwLock<TabWidgetState>>,
+    client: Arc<LinearClient>,
+    keymap: Arc<Keymap>,
 }
 
 #[derive(Debug, Clone)]
 struct TabWidgetState {
     selected_index: usize,
     tabs: Vec<Tab>,
+    // set when custom views couldn't be loaded, cleared on retry
+    error: Option<String>,
+    // configured startup view that wasn't loaded yet
+    startup_tab: Option<String>,
+    // tab change the app still has to act on
+    pending_change: Option<TabChangeEvent>,
+    // shown as a badge on the inbox tab
+    unread_count: usize,
 }
 
 impl Default for TabWidget {
@@ -31,12 +43,35 @@ impl Default for TabWidget {
         TabWidget {
             state: Arc::new(RwLock::new(TabWidgetState {
                 selected_index: 0,
-                tabs: vec![Tab {
-                    title: String::from("My Issues"),
-                    tab_type: TabType::MyIssues,
-                    custom_view: None,
-                }],
+                tabs: vec![
+                    Tab {
+                        title: String::from("My Issues"),
+                        tab_type: TabType::MyIssues,
+                        custom_view: None,
+                    },
+                    Tab {
+                        title: String::from("Inbox"),
+                        tab_type: TabType::Inbox,
+                        custom_view: None,
+                    },
+                    Tab {
+                        title: String::from("Projects"),
+                        tab_type: TabType::Projects,
+                        custom_view: None,
+                    },
+                    Tab {
+                        title: String::from("Cycles"),
+                        tab_type: TabType::Cycles,
+                        custom_view: None,
+                    },
+                ],
+                error: None,
+                startup_tab: None,
+                pending_change: None,
+                unread_count: 0,
             })),
+            client: Arc::default(),
+            keymap: Arc::default(),
         }
     }
 }
@@ -45,6 +80,9 @@ impl Default for TabWidget {
 enum TabType {
     #[default]
     MyIssues,
+    Inbox,
+    Projects,
+    Cycles,
     CustomView,
     SearchResults,
 }
@@ -57,33 +95,131 @@ struct Tab {
 }
 
 impl TabWidget {
+    pub fn new(client: Arc<LinearClient>, keymap: Arc<Keymap>) -> Self {
+        Self {
+            client,
+            keymap,
+            ..Default::default()
+        }
+    }
+
     pub fn run(&self) {
         let this = self.clone();
         tokio::spawn(this.fetch());
     }
 
     async fn fetch(self) {
-        let linear_api_token =
-            std::env::var("LINEAR_API_TOKEN").expect("Missing LINEAR_API_TOKEN env var");
-        let client = LinearClient::new(linear_api_token).unwrap();
+        let client = &self.client;
         let variables = custom_views_query::Variables {};
         match client.query(CustomViewsQuery, variables).await {
             Ok(data) => {
-                let mut state = self.state.write().unwrap();
-                for custom_view in data.custom_views.nodes.iter() {
-                    state.tabs.push(Tab {
-                        title: custom_view.name.clone(),
-                        tab_type: TabType::CustomView,
-                        custom_view: Some(custom_view.clone()),
-                    });
-                }
+                self.state.write().unwrap().error = None;
+                cache::save_views(CachedViews::new(data.custom_views.nodes.clone()));
+                self.set_custom_views(data.custom_views.nodes);
             }
-            Err(e) => {
-                panic!("Error {:#?}", e);
-                //return;
+            Err(e) => self.state.write().unwrap().error = Some(e.to_string()),
+        }
+    }
+
+    fn find_tab(tabs: &[Tab], name: &str) -> Option<usize> {
+        tabs.iter().position(|tab| {
+            tab.title.eq_ignore_ascii_case(name)
+                || tab
+                    .custom_view
+                    .as_ref()
+                    .is_some_and(|view| view.id == name || view.slug_id.as_deref() == Some(name))
+        })
+    }
+
+    fn change_event(tab: &Tab) -> TabChangeEvent {
+        match (&tab.custom_view, &tab.tab_type) {
+            (Some(custom_view), _) => TabChangeEvent::FetchCustomViewIssues(custom_view.clone()),
+            (None, TabType::MyIssues) => TabChangeEvent::FetchMyIssues,
+            (None, TabType::Inbox) => TabChangeEvent::ShowInbox,
+            (None, TabType::Projects) => TabChangeEvent::ShowProjects,
+            (None, TabType::Cycles) => TabChangeEvent::ShowCycles,
+            (None, TabType::SearchResults) => TabChangeEvent::SearchIssues,
+            (None, TabType::CustomView) => TabChangeEvent::None,
+        }
+    }
+
+    // selects the configured startup tab. A custom view that isn't loaded yet gets selected
+    // once it arrives, unless another tab was picked in the meantime.
+    pub fn select_startup_tab(&self, name: &str) -> TabChangeEvent {
+        let mut state = self.state.write().unwrap();
+        match Self::find_tab(&state.tabs, name) {
+            Some(index) => {
+                state.selected_index = index;
+                Self::change_event(&state.tabs[index])
             }
+            None => {
+                state.startup_tab = Some(name.to_string());
+                TabChangeEvent::FetchMyIssues
+            }
+        }
+    }
+
+    // called on every tick, so the write lock is only taken when the count changed
+    pub fn set_unread_count(&self, unread_count: usize) {
+        if self.state.read().unwrap().unread_count != unread_count {
+            self.state.write().unwrap().unread_count = unread_count;
+        }
+    }
+
+    pub fn take_tab_change(&self) -> Option<TabChangeEvent> {
+        self.state.write().unwrap().pending_change.take()
+    }
+
+    // fetches custom views again after a failed load
+    pub fn retry(&self) {
+        if self.state.write().unwrap().error.take().is_some() {
+            self.run();
+        }
+    }
+
+    pub fn load_cache(&self) {
+        if let Some(cached) = cache::load_views() {
+            self.set_custom_views(cached.views);
+        }
+    }
+
+    // swaps in a fresh list of custom views, keeping the selected tab where possible
+    fn set_custom_views(&self, views: Vec<custom_views_query::ViewFragment>) {
+        let mut state = self.state.write().unwrap();
+        let selected = state.tabs.get(state.selected_index).cloned();
+        let (fixed, rest): (Vec<Tab>, Vec<Tab>) = state
+            .tabs
+            .drain(..)
+            .filter(|tab| tab.tab_type != TabType::CustomView)
+            .partition(|tab| {
+                matches!(
+                    tab.tab_type,
+                    TabType::MyIssues | TabType::Inbox | TabType::Projects | TabType::Cycles
+                )
+            });
+        state.tabs = fixed;
+        state.tabs.extend(views.into_iter().map(|custom_view| Tab {
+            title: custom_view.name.clone(),
+            tab_type: TabType::CustomView,
+            custom_view: Some(custom_view),
+        }));
+        state.tabs.extend(rest);
+        state.selected_index = selected
+            .and_then(|selected| {
+                state.tabs.iter().position(|tab| {
+                    tab.tab_type == selected.tab_type && tab.custom_view == selected.custom_view
+                })
+            })
+            .unwrap_or(0);
+        if let Some(name) = state.startup_tab.take()
+            && state.selected_index == 0
+            && let Some(index) = Self::find_tab(&state.tabs, &name)
+        {
+            state.selected_index = index;
+            state.pending_change = Some(Self::change_event(&state.tabs[index]));
         }
     }
+
     pub fn next(&self) -> usize {
         let mut state = self.state.write().unwrap();
         if state.selected_index < state.tabs.len() - 1 {
@@ -113,43 +249,19 @@ impl TabWidget {
     }
 
     pub fn handle_event(&self, event: &Event) -> crate::TabChangeEvent {
-        if let Event::Key(key) = event {
-            if key.kind == KeyEventKind::Press {
-                match key.code {
-                    KeyCode::Tab => {
-                        let index = self.next();
-                        let state = self.state.read().unwrap();
-                        match &state.tabs[index].custom_view {
-                            Some(custom_view) => {
-                                return TabChangeEvent::FetchCustomViewIssues(custom_view.clone());
-                            }
-                            _ => {
-                                return match state.tabs[index].tab_type {
-                                    TabType::MyIssues => TabChangeEvent::FetchMyIssues,
-                                    TabType::SearchResults => TabChangeEvent::SearchIssues,
-                                    _ => TabChangeEvent::None,
-                                };
-                            }
-                        }
-                    }
-                    KeyCode::BackTab => {
-                        self.prev();
-                        let state = self.state.read().unwrap();
-                        match &state.tabs[state.selected_index].custom_view {
-                            Some(custom_view) => {
-                                return TabChangeEvent::FetchCustomViewIssues(custom_view.clone());
-                            }
-                            _ => {
-                                return match state.tabs[state.selected_index].tab_type {
-                                    TabType::MyIssues => TabChangeEvent::FetchMyIssues,
-                                    TabType::SearchResults => TabChangeEvent::SearchIssues,
-                                    _ => TabChangeEvent::None,
-                                };
-                            }
-                        }
-                    }
-                    _ => return TabChangeEvent::None,
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            match self.keymap.action(key) {
+                Some(Action::NextTab) => {
+                    let index = self.next();
+                    return Self::change_event(&self.state.read().unwrap().tabs[index]);
                 }
+                Some(Action::PrevTab) => {
+                    let index = self.prev();
+                    return Self::change_event(&self.state.read().unwrap().tabs[index]);
+                }
+                _ => return TabChangeEvent::None,
             }
         }
         TabChangeEvent::None
@@ -159,14 +271,26 @@ impl TabWidget {
 impl Widget for &TabWidget {
     fn render(self, area: Rect, buf: &mut Buffer) {
         use Constraint::{Length, Min};
-        let horizontal = Layout::horizontal([Length(23), Min(0)]);
-        let [header, main] = horizontal.areas(area);
+        let error = self.state.read().unwrap().error.clone().map(|error| {
+            Line::from(vec![
+                Span::from(format!("Custom views failed to load: {} ", error)).red(),
+                Span::from(format!(" <{}> ", self.keymap.label(Action::Retry))).blue(),
+                Span::from("to retry "),
+            ])
+        });
+        let error_width = error.as_ref().map_or(0, |error| error.width() as u16);
+        let horizontal = Layout::horizontal([Length(23), Min(0), Length(error_width)]);
+        let [header, main, error_area] = horizontal.areas(area);
+        if let Some(error) = error {
+            error.render(error_area, buf);
+        }
 
         Line::from(vec![
-            Span::from(" <tab> ").blue(),
+            Span::from(format!(" <{}> ", self.keymap.label(Action::NextTab))).blue(),
             Span::from("to change view:  "),
         ])
         .render(header, buf);
+        let unread_count = self.state.read().unwrap().unread_count;
         Tabs::new(
             self.state
                 .read()
@@ -187,15 +311,27 @@ impl Widget for &TabWidget {
                         )
                     } else if tab.tab_type == TabType::SearchResults {
                         (iconmap::ico_to_nf("Magnify"), Color::Yellow.to_string())
+                    } else if tab.tab_type == TabType::Inbox {
+                        (iconmap::ico_to_nf("Inbox"), Color::Magenta.to_string())
+                    } else if tab.tab_type == TabType::Projects {
+                        (iconmap::ico_to_nf("Cube"), Color::Green.to_string())
+                    } else if tab.tab_type == TabType::Cycles {
+                        (iconmap::ico_to_nf("Refresh"), Color::Cyan.to_string())
                     } else {
                         (iconmap::ico_to_nf("Home"), Color::Blue.to_string())
                     };
                     let project_color = Color::from_str(&color).unwrap();
-                    Span::from(format!("{} {}", icon, tab.title.clone().bold()))
-                        .fg(project_color)
-                        .bold()
+                    let mut title = Line::from(
+                        Span::from(format!("{} {}", icon, tab.title.clone().bold()))
+                            .fg(project_color)
+                            .bold(),
+                    );
+                    if tab.tab_type == TabType::Inbox && unread_count > 0 {
+                        title.push_span(Span::from(format!(" {}", unread_count)).red().bold());
+                    }
+                    title
                 })
-                .collect::<Vec<Span>>(),
+                .collect::<Vec<Line>>(),
         )
         .select(self.state.read().unwrap().selected_index)
         .padding(" ", " ")
@@ -208,30 +344,23 @@ impl Widget for &TabWidget {
 mod tests {
     use std::sync::{Arc, RwLock};
 
-    use crossterm::event::{KeyCode, KeyEventKind, KeyEventState, KeyModifiers};
+    use crossterm::event::KeyCode;
     use insta::assert_snapshot;
     use ratatui::{Terminal, backend::TestBackend};
 
     use crate::{
         TabChangeEvent,
         queries::custom_views_query,
-        widgets::{TabWidget, tab_widget::TabType},
+        stub_server::StubServer,
+        widgets::{TabWidget, create_key_event, tab_widget::TabType},
     };
 
     use super::{Tab, TabWidgetState};
 
-    fn create_key_event(code: KeyCode) -> crossterm::event::Event {
-        crossterm::event::Event::Key(crossterm::event::KeyEvent {
-            code,
-            kind: KeyEventKind::Press,
-            modifiers: KeyModifiers::empty(),
-            state: KeyEventState::empty(),
-        })
-    }
-
     #[test]
     fn test_empty_state() {
         let app = TabWidget::default();
+        app.set_unread_count(3);
         let mut terminal = Terminal::new(TestBackend::new(100, 2)).unwrap();
         terminal
             .draw(|frame| frame.render_widget(&app, frame.area()))
@@ -240,6 +369,21 @@ mod tests {
         assert_snapshot!(terminal.backend());
 
         let ev = app.handle_event(&create_key_event(KeyCode::Tab));
+        assert_eq!(ev, TabChangeEvent::ShowInbox);
+
+        let ev = app.handle_event(&create_key_event(KeyCode::Tab));
+        assert_eq!(ev, TabChangeEvent::ShowProjects);
+
+        let ev = app.handle_event(&create_key_event(KeyCode::Tab));
+        assert_eq!(ev, TabChangeEvent::ShowCycles);
+
+        let ev = app.handle_event(&create_key_event(KeyCode::BackTab));
+        assert_eq!(ev, TabChangeEvent::ShowProjects);
+
+        let ev = app.handle_event(&create_key_event(KeyCode::BackTab));
+        assert_eq!(ev, TabChangeEvent::ShowInbox);
+
+        let ev = app.handle_event(&create_key_event(KeyCode::BackTab));
         assert_eq!(ev, TabChangeEvent::FetchMyIssues);
 
         let ev = app.handle_event(&create_key_event(KeyCode::Char('j')));
@@ -280,7 +424,13 @@ mod tests {
                         }),
                     },
                 ],
+                error: None,
+                startup_tab: None,
+                pending_change: None,
+                unread_count: 0,
             })),
+            client: Arc::default(),
+            keymap: Arc::default(),
         };
 
         let mut terminal = Terminal::new(TestBackend::new(125, 2)).unwrap();
@@ -345,7 +495,6 @@ mod tests {
         let ev = app.handle_event(&create_key_event(KeyCode::BackTab));
         assert_eq!(ev, TabChangeEvent::FetchMyIssues);
 
-
         app.show_and_select_search_tab();
         terminal
             .draw(|frame| frame.render_widget(&app, frame.area()))
@@ -355,7 +504,100 @@ mod tests {
         assert_eq!(app.state.read().unwrap().selected_index, app.state.read().unwrap().tabs.len() - 1);
         // make sure the search tab is there
         assert_snapshot!(terminal.backend());
+    }
 
+    #[test]
+    fn test_set_custom_views() {
+        let view = |id: &str| custom_views_query::ViewFragment {
+            slug_id: Some(id.into()),
+            color: None,
+            icon: None,
+            id: id.into(),
+            name: id.into(),
+        };
+        let app = TabWidget::default();
+        app.set_custom_views(vec![view("a"), view("b")]);
+        app.show_and_select_search_tab();
+        app.prev();
+        assert_eq!(app.state.read().unwrap().selected_index, 5);
+
+        // refreshed views replace the old ones, search stays last and "b" stays selected
+        app.set_custom_views(vec![view("c"), view("b")]);
+        let state = app.state.read().unwrap();
+        let titles: Vec<&str> = state.tabs.iter().map(|tab| tab.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "My Issues",
+                "Inbox",
+                "Projects",
+                "Cycles",
+                "c",
+                "b",
+                "Search Results"
+            ]
+        );
+        assert_eq!(state.selected_index, 5);
+    }
+
+    #[test]
+    fn test_select_startup_tab() {
+        let view = |id: &str| custom_views_query::ViewFragment {
+            id: id.into(),
+            name: id.into(),
+            ..Default::default()
+        };
+        let app = TabWidget::default();
+        // views aren't loaded yet, so My Issues loads first
+        assert_eq!(app.select_startup_tab("b"), TabChangeEvent::FetchMyIssues);
+        assert_eq!(app.take_tab_change(), None);
+
+        app.set_custom_views(vec![view("a"), view("b")]);
+        assert_eq!(app.state.read().unwrap().selected_index, 5);
+        assert_eq!(
+            app.take_tab_change(),
+            Some(TabChangeEvent::FetchCustomViewIssues(view("b")))
+        );
+        assert_eq!(app.take_tab_change(), None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch() {
+        let server = StubServer::start();
+        let app = TabWidget::new(server.client(), Arc::default());
+        app.clone().fetch().await;
+
+        let state = app.state.read().unwrap();
+        let titles: Vec<&str> = state.tabs.iter().map(|tab| tab.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            [
+                "My Issues",
+                "Inbox",
+                "Projects",
+                "Cycles",
+                "Bugs",
+                "Roadmap"
+            ]
+        );
+        assert_eq!(server.operations(), ["CustomViewsQuery"]);
+    }
 
+    #[tokio::test]
+    async fn test_fetch_error() {
+        let server = StubServer::start();
+        server.respond_with(
+            "CustomViewsQuery",
+            r#"{"errors": [{"message": "Authentication required"}]}"#,
+        );
+        let app = TabWidget::new(server.client(), Arc::default());
+        app.clone().fetch().await;
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 1)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+        assert_eq!(app.state.read().unwrap().tabs.len(), 4);
     }
 }